@@ -1,5 +1,5 @@
 use clap::Parser;
-use domino_lib::{generate_puzzle, solve_puzzle, validate_puzzle, classify_puzzle, Puzzle, Tile};
+use domino_lib::{solve_puzzle, validate_puzzle, classify_puzzle, Puzzle, PuzzleGenerator, Tile};
 use serde_json::Value;
 
 #[derive(Parser)]
@@ -17,7 +17,9 @@ enum Commands {
         #[arg(short, long)]
         c: u32,
         #[arg(short, long, action)]
-        random: bool
+        random: bool,
+        #[arg(short, long)]
+        seed: Option<u64>
     },
     ValidatePuzzle {
         #[arg(short, long)]
@@ -39,10 +41,20 @@ fn main() {
     let cli = Cli::parse();
 
     match &cli.command {
-        Commands::GeneratePuzzle { n, c, random} => {
-
-            let puzzle = generate_puzzle(*n as usize, *c as usize, *random);
-            println!("Puzzle: {}", deserialize_puzzle(puzzle));
+        Commands::GeneratePuzzle { n, c, random, seed } => {
+            let mut generator = PuzzleGenerator::new(*n as usize)
+                .with_class(*c as usize)
+                .with_random(*random);
+            if let Some(seed) = seed {
+                generator = generator.with_seed(*seed);
+            }
+            match generator.generate() {
+                Ok(puzzle) => println!("Puzzle: {}", deserialize_puzzle(puzzle)),
+                Err(error) => {
+                    eprintln!("Error: {}", error);
+                    std::process::exit(1);
+                }
+            }
         },
         #[allow(unused_variables)]
         Commands::ValidatePuzzle { puzzle, solution } => {
@@ -1,9 +1,9 @@
 //! This module defines the `ComplexityClass` struct, which represents a class of computational complexity.
 //!
-//! The class is validated to ensure it falls within the defined range `[1, NUMBER_OF_CLASSES]`.
+//! The class is validated to ensure it falls within the range `[1, NUMBER_OF_CLASSES]` of its `ClassificationScheme`.
 //! It also implements `Display` for formatted output and `Into<f32>` for numeric conversion.
 
-use super::NUMBER_OF_CLASSES;
+use super::ClassificationScheme;
 use crate::DominoError;
 use std::cmp::Ordering;
 use std::fmt::Display;
@@ -28,10 +28,30 @@ impl ComplexityClass {
     /// * `Err(DominoError::InvalidClass)` - If the class is out of bounds.
     ///
     pub fn new(class: usize) -> Result<ComplexityClass, DominoError> {
-        if class == 0 || class > NUMBER_OF_CLASSES {
+        Self::with_scheme(class, &ClassificationScheme::default())
+    }
+
+    /// Creates a new `ComplexityClass` instance if the provided value is a class of the given scheme.
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - The complexity class value.
+    /// * `scheme` - The `ClassificationScheme` defining the valid classes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ComplexityClass)` - If the provided class is in the range `[1, scheme.number_of_classes()]`.
+    /// * `Err(DominoError::InvalidClass)` - If the class is out of bounds.
+    ///
+    pub fn with_scheme(
+        class: usize,
+        scheme: &ClassificationScheme,
+    ) -> Result<ComplexityClass, DominoError> {
+        let number_of_classes = scheme.number_of_classes();
+        if class == 0 || class > number_of_classes {
             let err_msg = format!(
                 "The complexity class provided is not valid: {}.\nIt should be in the range [1, {}]",
-                class, NUMBER_OF_CLASSES
+                class, number_of_classes
             );
             return Err(DominoError::InvalidClass(err_msg));
         }
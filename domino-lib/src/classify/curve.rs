@@ -0,0 +1,284 @@
+//! This module defines the `ThresholdCurve` trait, which places the boundaries between complexity classes.
+//!
+//! A curve maps each class `k` in `[1, NUMBER_OF_CLASSES]` to the upper bound of the relative complexities
+//! (in `[0.0, 1.0]`) falling into that class. The following curves are provided:
+//! - `FibonacciCurve`: class widths growing as the translated Fibonacci sequence (the default).
+//! - `LinearCurve`: classes of equal width.
+//! - `GeometricCurve`: class widths growing by a constant ratio.
+//! - `BreakpointCurve`: boundaries supplied by the user, e.g. calibrated on playtest data.
+//!
+//! Curves are compared through `CurveEq`, implemented for every curve that is `Eq`, so that schemes can be compared.
+
+use std::any::Any;
+
+use super::formula::compute_threshold;
+use crate::DominoError;
+
+/// Places the boundaries between the complexity classes of a `ClassificationScheme`.
+pub trait ThresholdCurve: std::fmt::Debug + Send + Sync + CurveEq {
+    /// Returns the upper bound of the relative complexities belonging to the class `k`.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The class index (1-based).
+    /// * `number_of_classes` - The number of classes of the scheme.
+    ///
+    /// # Returns
+    ///
+    /// The threshold as an `f32` in `[0.0, 1.0]`, non decreasing in `k` and equal to `1.0` for the last class.
+    fn threshold(&self, k: usize, number_of_classes: usize) -> f32;
+
+    /// Checks that the curve can bound the given number of classes.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_of_classes` - The number of classes of the scheme.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the curve fits the classes, which any curve computing its thresholds does.
+    /// * `Err(DominoError::InvalidClass)` - Otherwise.
+    fn check_classes(&self, number_of_classes: usize) -> Result<(), DominoError> {
+        let _ = number_of_classes;
+        Ok(())
+    }
+}
+
+/// Compares curves behind a `dyn ThresholdCurve`, implemented for every curve that is `Eq`.
+pub trait CurveEq {
+    /// Returns the curve as `Any`, to recover its concrete type.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns whether the other curve is of the same type and equal to this one.
+    fn eq_curve(&self, other: &dyn ThresholdCurve) -> bool;
+}
+
+impl<T: ThresholdCurve + Eq + 'static> CurveEq for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_curve(&self, other: &dyn ThresholdCurve) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<T>()
+            .is_some_and(|other| self == other)
+    }
+}
+
+/// Curve whose class widths follow the translated Fibonacci sequence, so that harder classes are wider.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FibonacciCurve;
+
+impl ThresholdCurve for FibonacciCurve {
+    fn threshold(&self, k: usize, number_of_classes: usize) -> f32 {
+        compute_threshold(k, number_of_classes)
+    }
+}
+
+/// Curve splitting the relative complexities into classes of equal width.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LinearCurve;
+
+impl ThresholdCurve for LinearCurve {
+    fn threshold(&self, k: usize, number_of_classes: usize) -> f32 {
+        k.min(number_of_classes) as f32 / number_of_classes as f32
+    }
+}
+
+/// Curve whose class widths grow by a constant ratio from one class to the next.
+#[derive(Debug, Clone, Copy)]
+pub struct GeometricCurve {
+    ratio: f32,
+}
+
+impl GeometricCurve {
+    /// Creates a new `GeometricCurve` with the given ratio between the widths of consecutive classes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ratio` - The ratio between the width of a class and the width of the previous one.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GeometricCurve)` - If the ratio is a finite positive number.
+    /// * `Err(DominoError::InvalidClass)` - Otherwise.
+    pub fn new(ratio: f32) -> Result<GeometricCurve, DominoError> {
+        if !ratio.is_finite() || ratio <= 0.0 {
+            return Err(DominoError::InvalidClass(format!(
+                "The ratio of a geometric curve must be positive, got {}",
+                ratio
+            )));
+        }
+
+        Ok(Self { ratio })
+    }
+}
+
+// The ratio is compared by its bits, which `new` keeps finite
+impl PartialEq for GeometricCurve {
+    fn eq(&self, other: &Self) -> bool {
+        self.ratio.to_bits() == other.ratio.to_bits()
+    }
+}
+
+impl Eq for GeometricCurve {}
+
+impl ThresholdCurve for GeometricCurve {
+    fn threshold(&self, k: usize, number_of_classes: usize) -> f32 {
+        let k = k.min(number_of_classes);
+        if self.ratio == 1.0 {
+            return LinearCurve.threshold(k, number_of_classes);
+        }
+        // Cumulative sum of the widths r^0, ..., r^(k-1), normalized by the sum of all the widths
+        (self.ratio.powi(k as i32) - 1.0) / (self.ratio.powi(number_of_classes as i32) - 1.0)
+    }
+}
+
+/// Curve with user supplied boundaries between the classes.
+///
+/// The `i`-th breakpoint is the upper bound of the class `i + 1`, the last class ending at `1.0`.
+/// A scheme using this curve must have one class more than the breakpoints.
+#[derive(Debug, Clone)]
+pub struct BreakpointCurve {
+    breakpoints: Vec<f32>,
+}
+
+impl BreakpointCurve {
+    /// Creates a new `BreakpointCurve` from the given boundaries.
+    ///
+    /// # Arguments
+    ///
+    /// * `breakpoints` - The upper bounds of the classes but the last one, strictly increasing in `(0.0, 1.0)`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BreakpointCurve)` - If the breakpoints are valid.
+    /// * `Err(DominoError::InvalidClass)` - If a breakpoint is out of range or they are not increasing.
+    pub fn new(breakpoints: Vec<f32>) -> Result<BreakpointCurve, DominoError> {
+        let in_range = breakpoints
+            .iter()
+            .all(|breakpoint| *breakpoint > 0.0 && *breakpoint < 1.0);
+        let increasing = breakpoints.windows(2).all(|pair| pair[0] < pair[1]);
+        if !in_range || !increasing {
+            return Err(DominoError::InvalidClass(format!(
+                "The breakpoints must be strictly increasing in (0, 1), got {:?}",
+                breakpoints
+            )));
+        }
+
+        Ok(Self { breakpoints })
+    }
+}
+
+// The breakpoints are compared by their bits, which `new` keeps in (0, 1)
+impl PartialEq for BreakpointCurve {
+    fn eq(&self, other: &Self) -> bool {
+        self.breakpoints.len() == other.breakpoints.len()
+            && self
+                .breakpoints
+                .iter()
+                .zip(&other.breakpoints)
+                .all(|(first, second)| first.to_bits() == second.to_bits())
+    }
+}
+
+impl Eq for BreakpointCurve {}
+
+impl ThresholdCurve for BreakpointCurve {
+    fn threshold(&self, k: usize, number_of_classes: usize) -> f32 {
+        if k == 0 {
+            return 0.0;
+        }
+        if k >= number_of_classes {
+            return 1.0;
+        }
+        self.breakpoints.get(k - 1).copied().unwrap_or(1.0)
+    }
+
+    fn check_classes(&self, number_of_classes: usize) -> Result<(), DominoError> {
+        if self.breakpoints.len() + 1 != number_of_classes {
+            return Err(DominoError::InvalidClass(format!(
+                "{} breakpoints bound {} classes, not {}",
+                self.breakpoints.len(),
+                self.breakpoints.len() + 1,
+                number_of_classes
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BreakpointCurve, CurveEq, FibonacciCurve, GeometricCurve, LinearCurve, ThresholdCurve,
+    };
+    use crate::DominoError;
+
+    fn assert_is_curve(curve: &dyn ThresholdCurve, number_of_classes: usize) {
+        let thresholds: Vec<f32> = (1..=number_of_classes)
+            .map(|k| curve.threshold(k, number_of_classes))
+            .collect();
+        assert!(thresholds.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!((thresholds[number_of_classes - 1] - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_curve_builtin_curves() {
+        for number_of_classes in [1, 3, 5, 10] {
+            assert_is_curve(&FibonacciCurve, number_of_classes);
+            assert_is_curve(&LinearCurve, number_of_classes);
+            assert_is_curve(&GeometricCurve::new(2.0).unwrap(), number_of_classes);
+            assert_is_curve(&GeometricCurve::new(0.5).unwrap(), number_of_classes);
+            assert_is_curve(&GeometricCurve::new(1.0).unwrap(), number_of_classes);
+        }
+    }
+
+    #[test]
+    fn test_curve_linear() {
+        assert_eq!(LinearCurve.threshold(1, 4), 0.25);
+        assert_eq!(LinearCurve.threshold(3, 4), 0.75);
+    }
+
+    #[test]
+    fn test_curve_geometric() {
+        // Widths 1, 2, 4 out of 7
+        let curve = GeometricCurve::new(2.0).unwrap();
+        assert_eq!(curve.threshold(1, 3), 1.0 / 7.0);
+        assert_eq!(curve.threshold(2, 3), 3.0 / 7.0);
+        assert!(matches!(
+            GeometricCurve::new(0.0),
+            Err(DominoError::InvalidClass(_))
+        ));
+    }
+
+    #[test]
+    fn test_curve_breakpoints() {
+        let curve = BreakpointCurve::new(vec![0.2, 0.7]).unwrap();
+        assert_is_curve(&curve, 3);
+        assert_eq!(curve.threshold(1, 3), 0.2);
+        assert_eq!(curve.threshold(2, 3), 0.7);
+        assert_eq!(curve.threshold(0, 3), 0.0);
+        assert!(BreakpointCurve::new(vec![0.7, 0.2]).is_err());
+        assert!(BreakpointCurve::new(vec![0.0, 0.5]).is_err());
+        assert!(curve.check_classes(3).is_ok());
+        assert!(matches!(
+            curve.check_classes(4),
+            Err(DominoError::InvalidClass(_))
+        ));
+    }
+
+    #[test]
+    fn test_curve_equality() {
+        let curve = GeometricCurve::new(2.0).unwrap();
+        assert!(curve.eq_curve(&GeometricCurve::new(2.0).unwrap()));
+        assert!(!curve.eq_curve(&GeometricCurve::new(0.5).unwrap()));
+        assert!(!curve.eq_curve(&LinearCurve));
+        assert_eq!(
+            BreakpointCurve::new(vec![0.2, 0.7]).unwrap(),
+            BreakpointCurve::new(vec![0.2, 0.7]).unwrap()
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! This module rates puzzles by the human deductions needed to complete them, rather than by their holes.
+//!
+//! The puzzle is completed step by step with the placements returned by `hint`. Whenever no placement
+//! can be deduced, the player has to guess: the guess is resolved with a tile of the solution, so that
+//! the rating only depends on the reasoning required and not on the luck of the player.
+
+use super::{ClassificationScheme, ComplexityClass};
+use crate::{hint, solve_puzzle_with, DominoError, HintReason, Puzzle, SolveMethod};
+
+/// Weight of a placement deduced from the only tile fitting a position.
+const ONLY_TILE_FOR_POSITION_WEIGHT: f32 = 0.1;
+/// Weight of a placement deduced from the only position fitting a tile.
+const ONLY_POSITION_FOR_TILE_WEIGHT: f32 = 0.4;
+/// Weight of a placement that could not be deduced and had to be guessed.
+const GUESS_WEIGHT: f32 = 1.0;
+
+/// The difficulty of a puzzle measured by the deductions needed to complete it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeductionRating {
+    /// The complexity class matching the `score`.
+    pub class: ComplexityClass,
+    /// The average weight of the placements, in `(0.0, 1.0]`.
+    pub score: f32,
+    /// The number of placements deduced from the only tile fitting a position.
+    pub only_tile_for_position: usize,
+    /// The number of placements deduced from the only position fitting a tile.
+    pub only_position_for_tile: usize,
+    /// The number of placements that had to be guessed.
+    pub guesses: usize,
+}
+
+/// Classifies the given puzzle by the deduction rules and the guesses required to complete it.
+///
+/// Each placement is weighted by the rule that produced it, harder rules and guesses weighting more,
+/// and the average weight is mapped to a `ComplexityClass` by `deduction_class`.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` to classify.
+///
+/// # Returns
+///
+/// * `Ok(DeductionRating)` - The rating of the puzzle, along with the rules and guesses counted. A puzzle
+///   without empty positions needs no placement, it has a score of `0.0` and is in the first class.
+/// * `Err(DominoError::EmptyPuzzle)` - If the puzzle has no tiles at all.
+/// * `Err(DominoError::UnsolvablePuzzle)` - If the puzzle has no completion.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length is invalid.
+pub fn classify_puzzle_by_deduction(puzzle: &Puzzle) -> Result<DeductionRating, DominoError> {
+    classify_puzzle_by_deduction_with(puzzle, &ClassificationScheme::default())
+}
+
+/// Classifies the given puzzle by deduction, into one of the classes of the given `ClassificationScheme`.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` to classify.
+/// * `scheme` - The `ClassificationScheme` defining the classes.
+///
+/// # Returns
+///
+/// * `Ok(DeductionRating)` - The rating of the puzzle, its class being in `[1, scheme.number_of_classes()]`.
+/// * `Err(DominoError)` - If an error occurs, as for `classify_puzzle_by_deduction`.
+pub fn classify_puzzle_by_deduction_with(
+    puzzle: &Puzzle,
+    scheme: &ClassificationScheme,
+) -> Result<DeductionRating, DominoError> {
+    if puzzle.0.iter().all(|tile| tile.is_none()) {
+        return Err(DominoError::EmptyPuzzle);
+    }
+    let solution = solve_puzzle_with(puzzle, SolveMethod::Backtracking)?;
+
+    let mut current = puzzle.clone();
+    let mut only_tile_for_position = 0;
+    let mut only_position_for_tile = 0;
+    let mut guesses = 0;
+    while let Some(position) = next_empty_position(&current) {
+        match hint(&current)? {
+            Some(hint) => {
+                match hint.reason {
+                    HintReason::OnlyTileForPosition { .. } => only_tile_for_position += 1,
+                    HintReason::OnlyPositionForTile => only_position_for_tile += 1,
+                }
+                current[hint.position] = Some(hint.tile);
+            }
+            None => {
+                guesses += 1;
+                current[position] = Some(solution[position]);
+            }
+        }
+    }
+
+    let placements = only_tile_for_position + only_position_for_tile + guesses;
+    let score = if placements == 0 {
+        0.0
+    } else {
+        (only_tile_for_position as f32 * ONLY_TILE_FOR_POSITION_WEIGHT
+            + only_position_for_tile as f32 * ONLY_POSITION_FOR_TILE_WEIGHT
+            + guesses as f32 * GUESS_WEIGHT)
+            / placements as f32
+    };
+    let class = deduction_class(score, scheme)?;
+
+    Ok(DeductionRating {
+        class,
+        score,
+        only_tile_for_position,
+        only_position_for_tile,
+        guesses,
+    })
+}
+
+/// Maps the average weight of the placements of a puzzle to a class of the scheme.
+///
+/// The thresholds of `classify_puzzle` bucket the share of the puzzle left empty, which says nothing
+/// about the deductions. The average weight instead ranges from `ONLY_TILE_FOR_POSITION_WEIGHT`, for a
+/// puzzle completed by looking at each position alone, to `GUESS_WEIGHT`, for a puzzle only completed
+/// by guessing, and this range is split into classes of equal width.
+///
+/// # Arguments
+///
+/// * `score` - The average weight of the placements.
+/// * `scheme` - The `ClassificationScheme` defining the classes.
+///
+/// # Returns
+///
+/// * `Ok(ComplexityClass)` - The class of the score, in `[1, scheme.number_of_classes()]`.
+/// * `Err(DominoError::InvalidClass)` - If the scheme has no class.
+fn deduction_class(
+    score: f32,
+    scheme: &ClassificationScheme,
+) -> Result<ComplexityClass, DominoError> {
+    let classes = scheme.number_of_classes();
+    let share = ((score - ONLY_TILE_FOR_POSITION_WEIGHT)
+        / (GUESS_WEIGHT - ONLY_TILE_FOR_POSITION_WEIGHT))
+        .clamp(0.0, 1.0);
+    let class = ((share * classes as f32).ceil() as usize).clamp(1, classes.max(1));
+    ComplexityClass::with_scheme(class, scheme)
+}
+
+/// Returns the position a player would guess first: the first empty position following a placed tile.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` being completed.
+///
+/// # Returns
+///
+/// The index of the position, or `None` if the puzzle is complete.
+fn next_empty_position(puzzle: &Puzzle) -> Option<usize> {
+    let len = puzzle.len();
+    (0..len)
+        .find(|&position| {
+            puzzle[position].is_none() && puzzle[(position + len - 1) % len].is_some()
+        })
+        .or_else(|| (0..len).find(|&position| puzzle[position].is_none()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        classify_puzzle_by_deduction, deduction_class, GUESS_WEIGHT, ONLY_TILE_FOR_POSITION_WEIGHT,
+    };
+    use crate::{ClassificationScheme, ComplexityClass, DominoError, Puzzle};
+
+    #[test]
+    fn test_deduction_class_thresholds() {
+        let scheme = ClassificationScheme::default();
+        assert_eq!(
+            deduction_class(ONLY_TILE_FOR_POSITION_WEIGHT, &scheme),
+            Ok(ComplexityClass(1))
+        );
+        assert_eq!(deduction_class(0.35, &scheme), Ok(ComplexityClass(1)));
+        assert_eq!(deduction_class(0.55, &scheme), Ok(ComplexityClass(2)));
+        assert_eq!(deduction_class(0.75, &scheme), Ok(ComplexityClass(3)));
+        assert_eq!(
+            deduction_class(GUESS_WEIGHT, &scheme),
+            Ok(ComplexityClass(3))
+        );
+
+        let scheme = ClassificationScheme::new(9).unwrap();
+        assert_eq!(deduction_class(0.15, &scheme), Ok(ComplexityClass(1)));
+        assert_eq!(
+            deduction_class(GUESS_WEIGHT, &scheme),
+            Ok(ComplexityClass(9))
+        );
+    }
+
+    #[test]
+    fn test_deduction_single_hole() {
+        let puzzle: Puzzle = vec![
+            Some((0, 0).into()),
+            Some((0, 1).into()),
+            Some((1, 1).into()),
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            None,
+            Some((3, 3).into()),
+            Some((3, 0).into()),
+        ]
+        .into();
+        let rating = classify_puzzle_by_deduction(&puzzle).unwrap();
+        assert_eq!(rating.only_tile_for_position, 1);
+        assert_eq!(rating.only_position_for_tile, 0);
+        assert_eq!(rating.guesses, 0);
+        assert_eq!(rating.class, ComplexityClass(1));
+    }
+
+    #[test]
+    fn test_deduction_requires_guesses() {
+        let puzzle: Puzzle = vec![
+            Some((0, 0).into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ]
+        .into();
+        let rating = classify_puzzle_by_deduction(&puzzle).unwrap();
+        assert!(rating.guesses > 0);
+        assert_eq!(
+            rating.only_tile_for_position + rating.only_position_for_tile + rating.guesses,
+            7
+        );
+        assert!(rating.class > ComplexityClass(1));
+    }
+
+    #[test]
+    fn test_deduction_full_puzzle() {
+        let puzzle: Puzzle = vec![
+            Some((0, 0).into()),
+            Some((0, 1).into()),
+            Some((1, 1).into()),
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            Some((2, 3).into()),
+            Some((3, 3).into()),
+            Some((3, 0).into()),
+        ]
+        .into();
+        let rating = classify_puzzle_by_deduction(&puzzle).unwrap();
+        assert_eq!(rating.score, 0.0);
+        assert_eq!(rating.guesses, 0);
+        assert_eq!(rating.class, ComplexityClass(1));
+    }
+
+    #[test]
+    fn test_deduction_empty_puzzle() {
+        let puzzle: Puzzle = vec![None; 8].into();
+        assert_eq!(
+            classify_puzzle_by_deduction(&puzzle),
+            Err(DominoError::EmptyPuzzle)
+        );
+    }
+
+    #[test]
+    fn test_deduction_unsolvable_puzzle() {
+        let puzzle: Puzzle = vec![
+            Some((0, 1).into()),
+            Some((2, 2).into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ]
+        .into();
+        assert_eq!(
+            classify_puzzle_by_deduction(&puzzle),
+            Err(DominoError::UnsolvablePuzzle)
+        );
+    }
+}
@@ -1,5 +1,4 @@
-// Global constant defining the default number of classes (thresholds)
-const NUMBER_OF_CLASSES: usize = 3; // Default value set to 3
+use super::ClassificationScheme;
 
 /// Computes the adjusted Fibonacci number at index `n`.
 ///
@@ -40,11 +39,15 @@ fn translated_fibonacci(n: usize) -> usize {
 /// D(NUMBER_OF_CLASSES) = F'(1) + F'(2) + ... + F'(NUMBER_OF_CLASSES)
 /// ```
 ///
+/// # Arguments
+///
+/// * `number_of_classes` - The number of classes (`NUMBER_OF_CLASSES`) of the classification scheme.
+///
 /// # Returns
 ///
 /// The sum of the first `NUMBER_OF_CLASSES` Fibonacci-translated numbers.
-fn compute_denominator() -> usize {
-    (1..=NUMBER_OF_CLASSES)
+fn compute_denominator(number_of_classes: usize) -> usize {
+    (1..=number_of_classes)
         .map(|i| translated_fibonacci(i))
         .sum()
 }
@@ -83,12 +86,13 @@ fn compute_intermediate_value(k: usize, denominator: usize) -> (usize, usize) {
 /// # Arguments
 ///
 /// * `k` - The threshold index (1-based).
+/// * `number_of_classes` - The number of classes (`NUMBER_OF_CLASSES`) of the classification scheme.
 ///
 /// # Returns
 ///
 /// The computed threshold as an `f32`, representing a fraction of the total.
-pub(super) fn compute_threshold(k: usize) -> f32 {
-    let denominator = compute_denominator() as f32;
+pub(super) fn compute_threshold(k: usize, number_of_classes: usize) -> f32 {
+    let denominator = compute_denominator(number_of_classes) as f32;
     let mut num_sum = 0;
 
     for i in 1..=k {
@@ -99,7 +103,7 @@ pub(super) fn compute_threshold(k: usize) -> f32 {
     num_sum as f32 / denominator
 }
 
-/// Recursively finds the index `k` at which the computed threshold surpasses a given `value`.
+/// Recursively finds the index `k` at which the threshold of the scheme surpasses a given `value`.
 ///
 /// The function starts from `k = 1` and increases `k` until:
 /// ```text
@@ -109,29 +113,32 @@ pub(super) fn compute_threshold(k: usize) -> f32 {
 /// # Arguments
 ///
 /// * `value` - The floating-point value to compare thresholds against.
+/// * `scheme` - The `ClassificationScheme` providing `NUMBER_OF_CLASSES` and the threshold curve.
 ///
 /// # Returns
 ///
 /// The first index `k` where the threshold surpasses `value`.
-pub(super) fn find_threshold_index(value: f32) -> usize {
+pub(super) fn find_threshold_index(value: f32, scheme: &ClassificationScheme) -> usize {
     if value == 0.0 {
       return 0;
     }
 
+    let number_of_classes = scheme.number_of_classes();
     let mut k = 1;
-    while k <= NUMBER_OF_CLASSES {
-        let threshold = compute_threshold(k);
+    while k <= number_of_classes {
+        let threshold = scheme.threshold(k);
         if threshold > value {
             return k;
         }
         k += 1;
     }
-    NUMBER_OF_CLASSES // Return the last index if value is not surpassed
+    number_of_classes // Return the last index if value is not surpassed
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::NUMBER_OF_CLASSES;
 
     /// Tests `translated_fibonacci` function with deterministic inputs.
     /// Verifies the computed Fibonacci-like sequence for fixed values.
@@ -150,14 +157,14 @@ mod tests {
     #[test]
     fn test_formula_compute_denominator() {
         let expected: usize = (1..=NUMBER_OF_CLASSES).map(translated_fibonacci).sum();
-        assert_eq!(compute_denominator(), expected);
+        assert_eq!(compute_denominator(NUMBER_OF_CLASSES), expected);
     }
 
     /// Tests `compute_intermediate_value` with deterministic `k` values.
     /// It verifies that the function correctly returns the Fibonacci-translated number and denominator.
     #[test]
     fn test_formula_compute_intermediate_value() {
-        let denominator = compute_denominator();
+        let denominator = compute_denominator(NUMBER_OF_CLASSES);
         let test_values = [1, 2, 3];
 
         for &k in &test_values {
@@ -171,11 +178,11 @@ mod tests {
     #[test]
     fn test_formula_compute_threshold() {
         let test_values = [1, 2, 3];
-        let denominator = compute_denominator() as f32;
+        let denominator = compute_denominator(NUMBER_OF_CLASSES) as f32;
 
         for &k in &test_values {
             let num_sum = (1..=k).map(translated_fibonacci).sum::<usize>() as f32;
-            assert_eq!(compute_threshold(k), num_sum / denominator);
+            assert_eq!(compute_threshold(k, NUMBER_OF_CLASSES), num_sum / denominator);
         }
     }
 
@@ -187,7 +194,7 @@ mod tests {
         let expected_indices = [0, 1, 2, 3, 3];
 
         for (value, expected) in test_values.iter().zip(expected_indices.iter()) {
-            assert_eq!(find_threshold_index(*value), *expected);
+            assert_eq!(find_threshold_index(*value, &ClassificationScheme::default()), *expected);
         }
     }
 }
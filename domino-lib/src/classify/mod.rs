@@ -1,9 +1,19 @@
-use crate::{utils::get_n, DominoError, Puzzle};
+use crate::{utils::{TileSet, Topology}, DominoError, Puzzle};
 pub use complexity_class::ComplexityClass;
-use formula::{compute_threshold, find_threshold_index};
+pub use curve::{
+    BreakpointCurve, CurveEq, FibonacciCurve, GeometricCurve, LinearCurve, ThresholdCurve,
+};
+pub use deduction::{
+    classify_puzzle_by_deduction, classify_puzzle_by_deduction_with, DeductionRating,
+};
+use formula::find_threshold_index;
+pub use scheme::ClassificationScheme;
 
 mod complexity_class;
+mod curve;
+mod deduction;
 mod formula;
+mod scheme;
 
 pub const NUMBER_OF_CLASSES: usize = 3;
 
@@ -24,33 +34,115 @@ pub const NUMBER_OF_CLASSES: usize = 3;
 /// * `Ok(ComplexityClass)` containing the computed ComplexityClass, or
 /// * `Err(DominoError)` if an error occurs (for example, if no holes are detected, the puzzle is empty, or if `get_n(puzzle)` fails).
 pub fn classify_puzzle(puzzle: &Puzzle) -> Result<ComplexityClass, DominoError> {
+    classify_puzzle_with(puzzle, &ClassificationScheme::default())
+}
+
+/// Classifies the given puzzle into one of the classes of the given `ClassificationScheme`.
+///
+/// The holes are measured exactly as in `classify_puzzle`, only the mapping of the resulting complexity
+/// to a class depends on the scheme.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the puzzle, represented as a `Vec<Option<Tile>>`.
+/// * `scheme` - The `ClassificationScheme` defining the classes.
+///
+/// # Returns
+///
+/// * `Ok(ComplexityClass)` containing the computed ComplexityClass, in `[1, scheme.number_of_classes()]`, or
+/// * `Err(DominoError)` if an error occurs, as for `classify_puzzle`.
+pub fn classify_puzzle_with(
+    puzzle: &Puzzle,
+    scheme: &ClassificationScheme,
+) -> Result<ComplexityClass, DominoError> {
+    classify_puzzle_with_topology(puzzle, scheme, Topology::Closed)
+}
+
+/// Classifies a puzzle of the given `Topology` into one of the classes of the given `ClassificationScheme`.
+///
+/// The holes of an open puzzle do not wrap around its ends, so empty positions at both ends are two holes.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the puzzle, represented as a `Vec<Option<Tile>>`.
+/// * `scheme` - The `ClassificationScheme` defining the classes.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// * `Ok(ComplexityClass)` containing the computed ComplexityClass, in `[1, scheme.number_of_classes()]`, or
+/// * `Err(DominoError)` if an error occurs, as for `classify_puzzle`.
+pub fn classify_puzzle_with_topology(
+    puzzle: &Puzzle,
+    scheme: &ClassificationScheme,
+    topology: Topology,
+) -> Result<ComplexityClass, DominoError> {
     // Check if the puzzle consists entirely of empty tiles
     if puzzle.0.iter().all(|tile| tile.is_none()) {
         return Err(DominoError::EmptyPuzzle); // Throw error if all tiles are empty
     }
-    // println!("puzzle: {puzzle:?}");
 
     // Retrieve the dimension of the puzzle (n) and propagate errors if any.
-    let n: usize = get_n(puzzle)? as usize;
+    let n: usize = topology.get_n(puzzle)?;
 
     // Calculate a derived length `l` based on the puzzle dimension.
-    // For even n: l = (n + 1) * (n + 2) / 2; for odd n: l = (n + 1) * (n + 1) / 2.
-    let l: usize = if n % 2 == 0 {
-        (n + 1) * (n + 2) / 2
-    } else {
-        (n + 1) * (n + 1) / 2
-    };
+    // For even n: l = (n + 1) * (n + 2) / 2; for odd n: l = (n + 1) * (n + 1) / 2, plus one for an open line.
+    let l: usize = topology.puzzle_length(n);
+
+    classify_holes(puzzle, scheme, n, l, topology)
+}
+
+/// Classifies a puzzle made of the tiles of an explicit `TileSet` into one of the classes of the given
+/// `ClassificationScheme`.
+///
+/// The holes are measured against the size of the set and its largest value, which take the place of the
+/// length and size of the double-`n` set.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the puzzle, represented as a `Vec<Option<Tile>>`.
+/// * `scheme` - The `ClassificationScheme` defining the classes.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// * `Ok(ComplexityClass)` containing the computed ComplexityClass, in `[1, scheme.number_of_classes()]`, or
+/// * `Err(DominoError::InvalidLength)` if the puzzle length differs from the size of the set, or
+/// * `Err(DominoError)` if another error occurs, as for `classify_puzzle`.
+pub fn classify_puzzle_with_tileset(
+    puzzle: &Puzzle,
+    scheme: &ClassificationScheme,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<ComplexityClass, DominoError> {
+    if puzzle.0.iter().all(|tile| tile.is_none()) {
+        return Err(DominoError::EmptyPuzzle);
+    }
+    if puzzle.len() != tileset.len() {
+        return Err(DominoError::InvalidLength);
+    }
 
+    classify_holes(puzzle, scheme, tileset.n(), tileset.len(), topology)
+}
+
+/// Classifies the holes of a puzzle of length `l` whose tiles have values up to `n`.
+fn classify_holes(
+    puzzle: &Puzzle,
+    scheme: &ClassificationScheme,
+    n: usize,
+    l: usize,
+    topology: Topology,
+) -> Result<ComplexityClass, DominoError> {
     // Compute the maximum allowed hole length in a generic puzzle leaving it valid.
     // If planar, subtract floor(n/2) from l; otherwise, subtract (n + 1) from l.
     let max_hole: usize = if n >= 4 { n + 1 } else { (n + 1) * 2 - 1 };
 
     // Detect holes within the puzzle. Each hole is represented as a tuple (start_index, end_index).
-    let holes: Vec<(usize, usize)> = detect_holes(puzzle);
+    let holes: Vec<(usize, usize)> = detect_holes_with(puzzle, topology);
 
     // Compute and return the complexity ComplexityClass based on the detected holes and derived metrics.
-    let class = compute_complexity(holes, max_hole, l);
-    class
+    compute_complexity(holes, max_hole, l, scheme)
 }
 
 /// Returns the range of the number of tiles to remove in a puzzle
@@ -60,6 +152,7 @@ pub fn classify_puzzle(puzzle: &Puzzle) -> Result<ComplexityClass, DominoError>
 ///
 /// * `class` - A `ComplexityClass` representing the puzzle's difficulty level.
 /// * `n` - The puzzle's dimension.
+/// * `scheme` - The `ClassificationScheme` the class belongs to.
 ///
 /// # Returns
 ///
@@ -71,7 +164,11 @@ pub fn classify_puzzle(puzzle: &Puzzle) -> Result<ComplexityClass, DominoError>
 ///
 /// This function panics if an invalid `ComplexityClass` is provided.
 #[allow(dead_code)]
-pub fn tiles_to_remove_range(class: ComplexityClass, n: usize) -> (usize, usize) {
+pub fn tiles_to_remove_range(
+    class: ComplexityClass,
+    n: usize,
+    scheme: &ClassificationScheme,
+) -> (usize, usize) {
     // Compute the derived length `l` based on the puzzle's dimension `n`
     let l = if n % 2 == 0 {
         (n + 1) * (n + 2) / 2
@@ -94,7 +191,7 @@ pub fn tiles_to_remove_range(class: ComplexityClass, n: usize) -> (usize, usize)
     // The class is in [1,NUMBER_OF_CLASSES] so panics only if the ComplexityClass implementation
     // changes and has no more the check on the values
     let (lower_relative, upper_relative) =
-        inverse_class_mapping(class).expect("The provided class is invalid");
+        inverse_class_mapping(class, scheme).expect("The provided class is invalid");
     // Invert the formula to compute the number of tiles to remove:
     //     t = relative_complexity * max_hole
     // so t must lie in:
@@ -117,7 +214,7 @@ pub fn tiles_to_remove_range(class: ComplexityClass, n: usize) -> (usize, usize)
     let mut max_tiles = std::cmp::min(max_tiles, max_hole as usize);
 
     // If the class is equal to the max NUMBER_OF_CLASSES, ensure the maximum is the maximum number of tiles removable.
-    if class == NUMBER_OF_CLASSES {
+    if class == scheme.number_of_classes() {
         max_tiles = max_hole as usize;
     }
 
@@ -139,23 +236,25 @@ pub fn tiles_to_remove_range(class: ComplexityClass, n: usize) -> (usize, usize)
 /// # Arguments
 ///
 /// * `class` - The computed class value (expected to be in `[1, NUMBER_OF_CLASSES]`)
+/// * `scheme` - The `ClassificationScheme` defining `NUMBER_OF_CLASSES`.
 ///
 /// # Returns
 ///
 /// A tuple `(min_x, max_x)` representing the range of decimal values that
 /// could have resulted in the given class, or `None` if the class is out of bounds.
 #[allow(dead_code)]
-fn inverse_class_mapping(class: ComplexityClass) -> Option<(f32, f32)> {
-    if class < 1 || class > NUMBER_OF_CLASSES {
+fn inverse_class_mapping(class: ComplexityClass, scheme: &ClassificationScheme) -> Option<(f32, f32)> {
+    let number_of_classes = scheme.number_of_classes();
+    if class < 1 || class > number_of_classes {
         return None;
     }
 
     let min_x = if class == 1 {
         0.0
     } else {
-        compute_threshold(class.0 - 1)
+        scheme.threshold(class.0 - 1)
     };
-    let max_x = compute_threshold(class.0);
+    let max_x = scheme.threshold(class.0);
 
     Some((min_x, max_x))
 }
@@ -171,8 +270,7 @@ fn inverse_class_mapping(class: ComplexityClass) -> Option<(f32, f32)> {
 /// * `holes` - A vector of tuples, each representing the start and end indices of a detected hole.
 /// * `max_hole` - The maximum allowed hole length for normalization purposes.
 /// * `len` - The derived length `l` computed from the puzzle's dimension.
-/// * `is_planar` - A boolean indicating whether the puzzle is planar (n ≤ 3).
-/// * `n` - The puzzle's dimension.
+/// * `scheme` - The `ClassificationScheme` defining the classes.
 ///
 /// # Returns
 ///
@@ -181,9 +279,10 @@ fn compute_complexity(
     holes: Vec<(usize, usize)>,
     max_hole: usize,
     len: usize,
+    scheme: &ClassificationScheme,
 ) -> Result<ComplexityClass, DominoError> {
     if holes.is_empty() {
-      return ComplexityClass::new(0)
+      return ComplexityClass::with_scheme(0, scheme)
     }
 
     // Calculate the absolute complexity from the detected holes
@@ -195,8 +294,8 @@ fn compute_complexity(
     let relative_complexity = absolute_complexity.clamp(0.0, 1.0);
 
     // Convert the relative complexity into an integer ComplexityClass.
-    let class = find_threshold_index(relative_complexity);
-    ComplexityClass::new(class)
+    let class = find_threshold_index(relative_complexity, scheme);
+    ComplexityClass::with_scheme(class, scheme)
 }
 
 /// Computes the absolute complexity of a puzzle based on its holes.
@@ -265,10 +364,25 @@ fn compute_absolute_complexity(holes: Vec<(usize, usize)>, max_hole: usize, len:
 /// - The first element is the starting index (inclusive) of the hole.
 /// - The second element is the index immediately after the last missing tile (exclusive).
 pub fn detect_holes(puzzle: &Puzzle) -> Vec<(usize, usize)> {
+    detect_holes_with(puzzle, Topology::Closed)
+}
+
+/// Detects the holes of a puzzle of the given `Topology`, as `detect_holes` does.
+///
+/// The holes of an open puzzle never wrap around: a hole reaching the last position ends there.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the puzzle represented as a `Vec<Option<Tile>>`.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// A vector of tuples `(start, end)`, the end being exclusive.
+pub fn detect_holes_with(puzzle: &Puzzle, topology: Topology) -> Vec<(usize, usize)> {
     let len = puzzle.0.len();
     let mut holes = Vec::new();
     let mut maybe_start: Option<usize> = None;
-    let mut wraps_around = false;
     let mut has_some = false;
 
     // Iterate through the puzzle to detect holes.
@@ -289,9 +403,8 @@ pub fn detect_holes(puzzle: &Puzzle) -> Vec<(usize, usize)> {
 
     // Handle the case where a hole extends to the end of the puzzle.
     if let Some(start) = maybe_start {
-        if !holes.is_empty() && holes[0].0 == 0 {
-            // If the first hole starts at index 0, it means the hole wraps around.
-            wraps_around = true;
+        if topology == Topology::Closed && !holes.is_empty() && holes[0].0 == 0 {
+            // If the first hole starts at index 0, the hole wraps around: extend the first hole backwards.
             holes[0] = (start, holes[0].1);
         } else {
             // Otherwise, add the hole as a separate entry.
@@ -299,13 +412,6 @@ pub fn detect_holes(puzzle: &Puzzle) -> Vec<(usize, usize)> {
         }
     }
 
-    // Merge the last and first holes if they form a cyclic sequence.
-    if wraps_around && holes.len() > 1 {
-        let first = holes.remove(0); // Remove the first hole (which starts at 0).
-        let last = holes.pop().unwrap(); // Take the last hole.
-        holes.insert(0, (last.0, first.1)); // Merge the two as a single hole.
-    }
-
     // If the entire puzzle is a hole (no `Some` elements exist), return it as a single hole.
     if !has_some {
         return vec![(0, len)];
@@ -317,6 +423,7 @@ pub fn detect_holes(puzzle: &Puzzle) -> Vec<(usize, usize)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::formula::compute_threshold;
     use crate::{ComplexityClass, DominoError, Puzzle, Tile};
 
     /// Creates a mock puzzle based on the given parameters.
@@ -340,7 +447,11 @@ mod tests {
         let mut cases = Vec::new();
         for n in [3, 4, 5, 6] {
             for class in 1..=NUMBER_OF_CLASSES {
-                let expected = tiles_to_remove_range(ComplexityClass::new(class).unwrap(), n);
+                let expected = tiles_to_remove_range(
+                    ComplexityClass::new(class).unwrap(),
+                    n,
+                    &ClassificationScheme::default(),
+                );
                 cases.push((ComplexityClass::new(class).unwrap(), n, expected));
             }
         }
@@ -351,7 +462,7 @@ mod tests {
     fn test_tiles_to_remove_range() {
         for (class, n, expected) in test_cases() {
             assert_eq!(
-                tiles_to_remove_range(class, n),
+                tiles_to_remove_range(class, n, &ClassificationScheme::default()),
                 expected,
                 "Failed for class {:?} and n = {}",
                 class,
@@ -382,6 +493,22 @@ mod tests {
             let puzzle = mock_puzzle(8, vec![]); // No holes
             assert_eq!(classify_puzzle(&puzzle), Err(DominoError::InvalidClass("The complexity class provided is not valid: 0.\nIt should be in the range [1, 3]".to_string())));
         }
+
+        /// Tests classification with a scheme using more classes than the default one.
+        ///
+        /// **Input:** Puzzles with a growing single hole, classified with 3 and 10 classes.
+        /// **Expected Output:** Classes in `[1, 10]`, never lower than the matching default class.
+        #[test]
+        fn test_classify_puzzle_with_scheme() {
+            let scheme = ClassificationScheme::new(10).unwrap();
+            for hole_size in 2..=7 {
+                let puzzle = mock_puzzle(8, (0..hole_size).collect());
+                let default_class = classify_puzzle(&puzzle).unwrap();
+                let class = classify_puzzle_with(&puzzle, &scheme).unwrap();
+                assert!(class >= 1 && class <= 10);
+                assert!(class >= default_class);
+            }
+        }
     }
 
     mod detect_holes_tests {
@@ -419,6 +546,61 @@ mod tests {
             let holes = detect_holes(&puzzle);
             assert_eq!(holes, vec![(6, 2)]);
         }
+
+        /// Tests detection of a wrapping hole alongside another hole.
+        ///
+        /// **Input:** A puzzle with holes at [4, 0] and [2].
+        /// **Expected Output:** Two holes, the wrapping one not merged with the other, where the
+        /// wraparound merge used to join them into the single hole `(2, 1)`.
+        #[test]
+        fn test_classify_detect_holes_wraparound_with_other_holes() {
+            let puzzle = mock_puzzle(5, vec![0, 2, 4]);
+            let holes = detect_holes(&puzzle);
+            assert_eq!(holes, vec![(4, 1), (2, 3)]);
+        }
+
+        /// Tests that a wrapping hole does not change the class of a puzzle.
+        ///
+        /// **Input:** Puzzles with holes of 2 and 1 tiles, the first one wrapping around or not.
+        /// **Expected Output:** The same class, where the wraparound merge used to see the wrapping puzzle
+        /// as a single hole of 7 tiles and classify it 3 instead of 1.
+        #[test]
+        fn test_classify_puzzle_wraparound_with_other_holes() {
+            let wrapping = mock_puzzle(8, vec![7, 0, 2]);
+            let inner = mock_puzzle(8, vec![1, 2, 4]);
+            assert_eq!(detect_holes(&wrapping), vec![(7, 1), (2, 3)]);
+            assert_eq!(classify_puzzle(&wrapping), classify_puzzle(&inner));
+        }
+
+        /// Tests that the holes of an open puzzle do not wrap around.
+        ///
+        /// **Input:** An open puzzle with holes at [0, 1] and [6, 7].
+        /// **Expected Output:** Two holes, one at each end.
+        #[test]
+        fn test_classify_detect_holes_open() {
+            let puzzle = mock_puzzle(8, vec![6, 7, 0, 1]);
+            let holes = detect_holes_with(&puzzle, Topology::Open);
+            assert_eq!(holes, vec![(0, 2), (6, 8)]);
+        }
+
+        /// Tests that classifying over the double-`n` set matches the classification of the size of the puzzle.
+        ///
+        /// **Input:** A puzzle of length 8 classified with `TileSet::regular(3)`, then with a set of another size.
+        /// **Expected Output:** The same class as `classify_puzzle`, then `DominoError::InvalidLength`.
+        #[test]
+        fn test_classify_puzzle_with_tileset() {
+            let puzzle = mock_puzzle(8, vec![2, 3, 4]);
+            let scheme = ClassificationScheme::default();
+            let tileset = TileSet::regular(3);
+            assert_eq!(
+                classify_puzzle_with_tileset(&puzzle, &scheme, &tileset, Topology::Closed),
+                classify_puzzle(&puzzle)
+            );
+            assert_eq!(
+                classify_puzzle_with_tileset(&puzzle, &scheme, &TileSet::regular(4), Topology::Closed),
+                Err(DominoError::InvalidLength)
+            );
+        }
     }
 
     #[cfg(test)]
@@ -461,10 +643,15 @@ mod tests {
             for hole_size in 2..=max_hole as usize {
                 let puzzle = create_puzzle_with_hole(n, hole_size, 0);
                 let holes = detect_holes(&puzzle);
-                let complexity = compute_complexity(holes, max_hole, total_size);
+                let complexity = compute_complexity(
+                    holes,
+                    max_hole,
+                    total_size,
+                    &ClassificationScheme::default(),
+                );
                 let expected_abs = (hole_size.saturating_sub(1) as f32 / max_hole as f32).powf(2.0);
                 let expected_rel = expected_abs.clamp(0.0, 1.0);
-                let expected_class = find_threshold_index(expected_rel);
+                let expected_class = find_threshold_index(expected_rel, &ClassificationScheme::default());
 
                 assert_eq!(
                     complexity.ok(),
@@ -488,7 +675,12 @@ mod tests {
             let puzzle = create_puzzle_with_hole(n, max_hole as usize, 0);
 
             let holes = detect_holes(&puzzle);
-            let complexity = compute_complexity(holes, max_hole, total_size);
+            let complexity = compute_complexity(
+                holes,
+                max_hole,
+                total_size,
+                &ClassificationScheme::default(),
+            );
 
             assert!(complexity.is_ok(), "Failed for a single large hole");
         }
@@ -505,7 +697,12 @@ mod tests {
             let puzzle = mock_puzzle(total_size, vec![2, 3, 5]);
 
             let holes = detect_holes(&puzzle);
-            let complexity = compute_complexity(holes, max_hole, total_size);
+            let complexity = compute_complexity(
+                holes,
+                max_hole,
+                total_size,
+                &ClassificationScheme::default(),
+            );
 
             assert!(complexity.is_ok(), "Failed for multiple small holes");
         }
@@ -519,11 +716,11 @@ mod tests {
     fn test_classify_inverse_class_mapping() {
         for class_value in 1..=NUMBER_OF_CLASSES {
             let class = ComplexityClass::new(class_value).unwrap();
-            let result = inverse_class_mapping(class);
+            let result = inverse_class_mapping(class, &ClassificationScheme::default());
             assert!(result.is_some());
             let (min_x, max_x) = result.unwrap();
-            let expected_min_x = compute_threshold(class_value - 1);
-            let expected_max_x = compute_threshold(class_value);
+            let expected_min_x = compute_threshold(class_value - 1, NUMBER_OF_CLASSES);
+            let expected_max_x = compute_threshold(class_value, NUMBER_OF_CLASSES);
 
             assert_eq!(min_x, expected_min_x);
             assert_eq!(max_x, expected_max_x);
@@ -0,0 +1,167 @@
+//! This module defines the `ClassificationScheme` struct, which describes how puzzles are split into complexity classes.
+//!
+//! The default scheme uses `NUMBER_OF_CLASSES` classes bounded by a `FibonacciCurve`, but both the number
+//! of classes and the `ThresholdCurve` can be chosen at runtime.
+
+use std::sync::Arc;
+
+use super::{FibonacciCurve, ThresholdCurve, NUMBER_OF_CLASSES};
+use crate::DominoError;
+
+/// Describes the complexity classes puzzles are classified into.
+///
+/// Two schemes are equal when they have as many classes and equal curves of the same type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassificationScheme {
+    number_of_classes: usize,
+    curve: SchemeCurve,
+}
+
+/// The curve of a scheme, compared through `CurveEq`.
+#[derive(Debug, Clone)]
+struct SchemeCurve(Arc<dyn ThresholdCurve>);
+
+impl PartialEq for SchemeCurve {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_curve(other.0.as_ref())
+    }
+}
+
+impl Eq for SchemeCurve {}
+
+impl ClassificationScheme {
+    /// Creates a new `ClassificationScheme` with the given number of classes, bounded by a `FibonacciCurve`.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_of_classes` - The number of complexity classes, the classes being `[1, number_of_classes]`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ClassificationScheme)` - If the number of classes is at least 1.
+    /// * `Err(DominoError::InvalidClass)` - If the number of classes is 0.
+    pub fn new(number_of_classes: usize) -> Result<ClassificationScheme, DominoError> {
+        Self::with_curve(number_of_classes, FibonacciCurve)
+    }
+
+    /// Creates a new `ClassificationScheme` with the given number of classes and threshold curve.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_of_classes` - The number of complexity classes, the classes being `[1, number_of_classes]`.
+    /// * `curve` - The `ThresholdCurve` placing the boundaries between the classes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ClassificationScheme)` - If the number of classes is at least 1 and fits the curve.
+    /// * `Err(DominoError::InvalidClass)` - If the number of classes is 0, or not the number of classes
+    ///   of a `BreakpointCurve`.
+    pub fn with_curve(
+        number_of_classes: usize,
+        curve: impl ThresholdCurve + 'static,
+    ) -> Result<ClassificationScheme, DominoError> {
+        if number_of_classes == 0 {
+            return Err(DominoError::InvalidClass(
+                "A classification scheme needs at least one class".to_string(),
+            ));
+        }
+        curve.check_classes(number_of_classes)?;
+
+        Ok(Self {
+            number_of_classes,
+            curve: SchemeCurve(Arc::new(curve)),
+        })
+    }
+
+    /// Returns the number of complexity classes of the scheme.
+    pub fn number_of_classes(&self) -> usize {
+        self.number_of_classes
+    }
+
+    /// Returns the `ThresholdCurve` of the scheme.
+    pub fn curve(&self) -> &dyn ThresholdCurve {
+        self.curve.0.as_ref()
+    }
+
+    /// Returns the upper bound of the relative complexities belonging to the class `k`.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The class index (1-based).
+    ///
+    /// # Returns
+    ///
+    /// The threshold of the class according to the curve of the scheme.
+    pub fn threshold(&self, k: usize) -> f32 {
+        self.curve.0.threshold(k, self.number_of_classes)
+    }
+}
+
+impl Default for ClassificationScheme {
+    /// Returns the scheme with `NUMBER_OF_CLASSES` classes bounded by a `FibonacciCurve`.
+    fn default() -> Self {
+        Self {
+            number_of_classes: NUMBER_OF_CLASSES,
+            curve: SchemeCurve(Arc::new(FibonacciCurve)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClassificationScheme;
+    use crate::{
+        classify::{BreakpointCurve, GeometricCurve, LinearCurve},
+        DominoError, NUMBER_OF_CLASSES,
+    };
+
+    #[test]
+    fn test_scheme_default() {
+        assert_eq!(
+            ClassificationScheme::default().number_of_classes(),
+            NUMBER_OF_CLASSES
+        );
+    }
+
+    #[test]
+    fn test_scheme_no_classes() {
+        assert!(matches!(
+            ClassificationScheme::new(0),
+            Err(DominoError::InvalidClass(_))
+        ));
+    }
+
+    #[test]
+    fn test_scheme_with_curve() {
+        let scheme = ClassificationScheme::with_curve(4, LinearCurve).unwrap();
+        assert_eq!(scheme.threshold(1), 0.25);
+        assert_eq!(scheme.threshold(4), 1.0);
+    }
+
+    #[test]
+    fn test_scheme_breakpoint_count() {
+        let curve = BreakpointCurve::new(vec![0.2, 0.7]).unwrap();
+        let scheme = ClassificationScheme::with_curve(3, curve.clone()).unwrap();
+        assert_eq!(scheme.threshold(0), 0.0);
+        assert!(matches!(
+            ClassificationScheme::with_curve(5, curve),
+            Err(DominoError::InvalidClass(_))
+        ));
+    }
+
+    #[test]
+    fn test_scheme_equality() {
+        assert_eq!(
+            ClassificationScheme::default(),
+            ClassificationScheme::new(NUMBER_OF_CLASSES).unwrap()
+        );
+        assert_ne!(
+            ClassificationScheme::new(4).unwrap(),
+            ClassificationScheme::with_curve(4, LinearCurve).unwrap()
+        );
+        assert_eq!(
+            ClassificationScheme::with_curve(4, GeometricCurve::new(2.0).unwrap()).unwrap(),
+            ClassificationScheme::with_curve(4, GeometricCurve::new(2.0).unwrap()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! This module provides the generation of packs of distinct puzzles, spread over several threads.
+//!
+//! Candidates are generated in rounds and deduplicated in the order of their index, so that a seeded
+//! `PuzzleGenerator` always yields the same pack whatever the number of threads.
+
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::{generator::hole_lengths, PuzzleGenerator};
+use crate::{
+    classify_puzzle_with_topology, utils::canonical_key, ComplexityClass, DominoError, Puzzle,
+    PuzzleEquivalence,
+};
+
+/// The number of candidates generated for each puzzle of the pack before giving up on finding distinct ones.
+const MAX_CANDIDATES_PER_PUZZLE: usize = 10;
+
+/// A puzzle of a pack, along with the data describing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedPuzzle {
+    /// The generated puzzle.
+    pub puzzle: Puzzle,
+    /// The complexity class of the puzzle in the scheme of the generator, if it can be classified.
+    pub class: Option<ComplexityClass>,
+    /// The number of holes, runs of consecutive empty positions, of the puzzle.
+    pub holes: usize,
+    /// The time spent generating the puzzle.
+    pub generation_time: Duration,
+}
+
+/// Generates `count` distinct puzzles matching the options of `spec`, using all the available threads.
+///
+/// Two puzzles are considered the same when one is a rotation or a reflection of the cycle of the other,
+/// or a reflection of the line of the other in the open topology.
+/// The budget of `spec` bounds the generation of each puzzle, a candidate whose generation fails is skipped
+/// like a duplicate. When `spec` has a seed, the `i`-th candidate is generated from the seed incremented by `i`,
+/// so the pack is reproducible.
+///
+/// # Arguments
+///
+/// * `spec` - The `PuzzleGenerator` describing the puzzles of the pack.
+/// * `count` - The number of puzzles of the pack.
+///
+/// # Returns
+///
+/// * `Ok(Vec<GeneratedPuzzle>)` - The `count` distinct puzzles with their data.
+/// * `Err(DominoError::GenerationError)` - If not enough distinct puzzles are found among the candidates.
+/// * `Err(DominoError)` - The error of the first candidate, as for `PuzzleGenerator::generate`, if every
+///   candidate failed.
+pub fn generate_batch(
+    spec: &PuzzleGenerator,
+    count: usize,
+) -> Result<Vec<GeneratedPuzzle>, DominoError> {
+    generate_batch_with(spec, count, PuzzleEquivalence::default())
+}
+
+/// Generates `count` puzzles matching the options of `spec` that are distinct up to the given equivalence.
+///
+/// # Arguments
+///
+/// * `spec` - The `PuzzleGenerator` describing the puzzles of the pack.
+/// * `count` - The number of puzzles of the pack.
+/// * `equivalence` - The `PuzzleEquivalence` defining the puzzles considered the same.
+///
+/// # Returns
+///
+/// * `Ok(Vec<GeneratedPuzzle>)` - The `count` distinct puzzles with their data.
+/// * `Err(DominoError)` - If an error occurs, as for `generate_batch`.
+pub fn generate_batch_with(
+    spec: &PuzzleGenerator,
+    count: usize,
+    equivalence: PuzzleEquivalence,
+) -> Result<Vec<GeneratedPuzzle>, DominoError> {
+    let workers = thread::available_parallelism()
+        .map_or(1, |workers| workers.get())
+        .min(count.max(1));
+    let max_candidates = count * MAX_CANDIDATES_PER_PUZZLE;
+
+    let mut batch: Vec<GeneratedPuzzle> = Vec::with_capacity(count);
+    let mut seen: HashSet<Vec<Option<(i32, i32)>>> = HashSet::new();
+    let mut next_candidate = 0;
+    let mut first_error: Option<DominoError> = None;
+    let mut failed = 0;
+    while batch.len() < count {
+        if next_candidate >= max_candidates {
+            // Options that no puzzle can meet make every candidate fail, report why
+            if let Some(error) = first_error.filter(|_| failed == max_candidates) {
+                return Err(error);
+            }
+            return Err(DominoError::GenerationError(format!(
+                "only {} distinct puzzles out of {} were found in {} candidates, {} of which failed",
+                batch.len(),
+                count,
+                max_candidates,
+                failed
+            )));
+        }
+
+        // Generate as many candidates as the puzzles missing, the duplicates and failures are replaced
+        // in the next round
+        let round = (count - batch.len()).min(max_candidates - next_candidate);
+        let candidates = generate_round(spec, next_candidate, round, workers);
+        next_candidate += round;
+
+        for candidate in candidates {
+            match candidate {
+                Ok(candidate) => {
+                    if batch.len() < count
+                        && seen.insert(canonical_key(&candidate.puzzle, equivalence, spec.topology()))
+                    {
+                        batch.push(candidate);
+                    }
+                }
+                Err(error) => {
+                    failed += 1;
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+    }
+
+    Ok(batch)
+}
+
+/// Generates the candidates of indices `[first, first + round)` over `workers` threads.
+///
+/// # Returns
+///
+/// The result of the generation of each candidate, in the order of their index.
+fn generate_round(
+    spec: &PuzzleGenerator,
+    first: usize,
+    round: usize,
+    workers: usize,
+) -> Vec<Result<GeneratedPuzzle, DominoError>> {
+    let next = AtomicUsize::new(first);
+    let mut candidates: Vec<(usize, Result<GeneratedPuzzle, DominoError>)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers.min(round))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut generated = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        if index >= first + round {
+                            break generated;
+                        }
+                        generated.push((index, generate_candidate(spec, index)));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("A generation thread panicked"))
+            .collect()
+    });
+
+    candidates.sort_by_key(|(index, _)| *index);
+    candidates
+        .into_iter()
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Generates the candidate of the given index, timing it and collecting its data.
+fn generate_candidate(spec: &PuzzleGenerator, index: usize) -> Result<GeneratedPuzzle, DominoError> {
+    let start = Instant::now();
+    let puzzle = match spec.seed() {
+        Some(seed) => {
+            spec.generate_with_rng(&mut StdRng::seed_from_u64(seed.wrapping_add(index as u64)))?
+        }
+        None => spec.generate_with_rng(&mut rand::thread_rng())?,
+    };
+    let generation_time = start.elapsed();
+
+    Ok(GeneratedPuzzle {
+        class: classify_puzzle_with_topology(&puzzle, spec.scheme(), spec.topology()).ok(),
+        holes: hole_lengths(&puzzle.0, spec.topology()).len(),
+        generation_time,
+        puzzle,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_batch, generate_batch_with};
+    use crate::{DominoError, GenerationBudget, PuzzleEquivalence, PuzzleGenerator};
+
+    #[test]
+    fn test_batch_distinct_puzzles() {
+        let spec = PuzzleGenerator::new(6).with_class(2).with_seed(11);
+        let batch = generate_batch(&spec, 8).unwrap();
+        assert_eq!(batch.len(), 8);
+        for (i, first) in batch.iter().enumerate() {
+            for second in &batch[i + 1..] {
+                assert!(!first.puzzle.is_equivalent(&second.puzzle));
+            }
+        }
+        assert!(batch.iter().all(|generated| generated.holes > 0));
+        assert!(batch
+            .iter()
+            .all(|generated| generated.class.is_some_and(|class| class.0 == 2)));
+    }
+
+    #[test]
+    fn test_batch_seeded() {
+        let spec = PuzzleGenerator::new(3).with_class(1).with_seed(5);
+        let puzzles = |batch: Vec<super::GeneratedPuzzle>| {
+            batch
+                .into_iter()
+                .map(|generated| generated.puzzle.0)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            puzzles(generate_batch(&spec, 3).unwrap()),
+            puzzles(generate_batch(&spec, 3).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_batch_relabelling() {
+        let spec = PuzzleGenerator::new(3).with_class(2).with_seed(3);
+        let equivalence = PuzzleEquivalence::RotationReflectionAndRelabelling;
+        let batch = generate_batch_with(&spec, 4, equivalence).unwrap();
+        for (i, first) in batch.iter().enumerate() {
+            for second in &batch[i + 1..] {
+                assert!(!first.puzzle.is_equivalent_with(&second.puzzle, equivalence));
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_not_enough_puzzles() {
+        // Few puzzles of length 8 miss a single tile, far less than requested
+        let spec = PuzzleGenerator::new(3).with_holes(1).with_max_hole_length(1);
+        assert!(matches!(
+            generate_batch(&spec, 200),
+            Err(DominoError::GenerationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_batch_failed_candidates() {
+        // Without restarts, the generation of most candidates ends on a dead end
+        let spec = PuzzleGenerator::new(4)
+            .with_class(3)
+            .with_seed(0)
+            .with_budget(GenerationBudget::new(None, Some(0)));
+        assert!((0..3).any(|seed| spec.clone().with_seed(seed).generate().is_err()));
+        let batch = generate_batch(&spec, 3).unwrap();
+        assert_eq!(batch.len(), 3);
+        assert!(batch
+            .iter()
+            .all(|generated| generated.class.is_some_and(|class| class.0 == 3)));
+
+        // The error of options that no puzzle meets is reported as is
+        assert!(matches!(
+            generate_batch(&PuzzleGenerator::new(4).with_class(10), 2),
+            Err(DominoError::InvalidClass(_))
+        ));
+    }
+}
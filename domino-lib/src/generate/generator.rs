@@ -0,0 +1,678 @@
+//! This module defines the `PuzzleGenerator` builder, which exposes every option of the generation.
+//!
+//! Tiles are removed one at a time from a random solution, rolling back the removals that make the puzzle
+//! ambiguous or too complex, until the targets are met. When a solution leads nowhere the generator restarts
+//! from another one, within the limits of its `GenerationBudget`. With a `HoleTemplate` the empty positions are
+//! fixed instead, and solutions are drawn until one makes the template a unique puzzle.
+
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{
+    check_solution_chain, create_solution_from_cycle, template::template_layout, GenerationBudget,
+    HoleTemplate,
+};
+use crate::{
+    classify_puzzle_with_topology, solve_all_with_topology,
+    utils::{find_eulerian_cycle_with_rng, find_eulerian_path_with_rng},
+    validate_puzzle_with_topology, ClassificationScheme, ComplexityClass, DominoError, Graph, Puzzle,
+    Solution, Tile, Topology,
+};
+
+/// The least number of removals tried on a solution before restarting from another one.
+const MIN_ATTEMPTS_BEFORE_RESTART: usize = 10;
+/// The draws spent looking for an empty position, from which the next tile to remove is searched.
+const HOLE_EXTENSION_DRAWS: usize = 10;
+
+/// Builds puzzles of a given size according to the chosen options.
+///
+/// At least a target class, a number of holes or a `HoleTemplate` has to be set; when several are set the
+/// generated puzzle meets all of them.
+#[derive(Debug, Clone)]
+pub struct PuzzleGenerator {
+    n: usize,
+    class: Option<usize>,
+    scheme: ClassificationScheme,
+    seed: Option<u64>,
+    random: bool,
+    holes: Option<usize>,
+    min_hole_length: usize,
+    max_hole_length: Option<usize>,
+    anchors: Vec<Tile>,
+    allow_doubles: bool,
+    budget: GenerationBudget,
+    solution: Option<Solution>,
+    template: Option<HoleTemplate>,
+    topology: Topology,
+}
+
+impl PuzzleGenerator {
+    /// Creates a new `PuzzleGenerator` for puzzles of size `n`.
+    ///
+    /// By default tiles are removed at random, doubles may be removed, holes have any length and the
+    /// generation is bounded by the default `GenerationBudget`.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The size of the puzzle.
+    ///
+    /// # Returns
+    ///
+    /// The `PuzzleGenerator` with the default options.
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            class: None,
+            scheme: ClassificationScheme::default(),
+            seed: None,
+            random: true,
+            holes: None,
+            min_hole_length: 1,
+            max_hole_length: None,
+            anchors: Vec::new(),
+            allow_doubles: true,
+            budget: GenerationBudget::default(),
+            solution: None,
+            template: None,
+            topology: Topology::Closed,
+        }
+    }
+
+    /// Sets the target complexity class, in `[1, scheme.number_of_classes()]`.
+    pub fn with_class(mut self, class: usize) -> Self {
+        self.class = Some(class);
+        self
+    }
+
+    /// Sets the `ClassificationScheme` the target class belongs to.
+    pub fn with_scheme(mut self, scheme: ClassificationScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Sets the seed of the random number generator, making the generation reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets whether tiles are removed at random or sequentially, each removal following the previous one.
+    pub fn with_random(mut self, random: bool) -> Self {
+        self.random = random;
+        self
+    }
+
+    /// Sets the exact number of holes, a hole being a run of consecutive empty positions.
+    pub fn with_holes(mut self, holes: usize) -> Self {
+        self.holes = Some(holes);
+        self
+    }
+
+    /// Sets the least number of consecutive empty positions of every hole.
+    pub fn with_min_hole_length(mut self, min_hole_length: usize) -> Self {
+        self.min_hole_length = min_hole_length;
+        self
+    }
+
+    /// Sets the largest number of consecutive empty positions of every hole.
+    pub fn with_max_hole_length(mut self, max_hole_length: usize) -> Self {
+        self.max_hole_length = Some(max_hole_length);
+        self
+    }
+
+    /// Adds a tile that is never removed, in either orientation.
+    pub fn with_anchor(mut self, tile: Tile) -> Self {
+        self.anchors.push(tile);
+        self
+    }
+
+    /// Sets whether doubles, tiles like `[2,2]`, may be removed.
+    pub fn with_doubles(mut self, allow_doubles: bool) -> Self {
+        self.allow_doubles = allow_doubles;
+        self
+    }
+
+    /// Sets the time allowed to the generation, keeping the restarts allowed by the budget.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.budget.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the `GenerationBudget` bounding the generation.
+    pub fn with_budget(mut self, budget: GenerationBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Sets the solution tiles are removed from, instead of a random Eulerian cycle or path.
+    ///
+    /// The solution must be made of the whole tileset of size `n` in the `Topology` of the generator: a closed
+    /// cycle, whose last tile matches the first one, or an open line, whose ends are free. The restarts remove
+    /// tiles again from the same solution.
+    pub fn with_solution(mut self, solution: Solution) -> Self {
+        self.solution = Some(solution);
+        self
+    }
+
+    /// Sets the `HoleTemplate` fixing the empty positions of the puzzle, in place of the number and lengths of holes.
+    ///
+    /// Solutions are drawn until one leads to a unique puzzle once the template is applied, keeping the anchors
+    /// and, if they may not be removed, the doubles. A target class has to be the class of the template.
+    pub fn with_template(mut self, template: HoleTemplate) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    /// Sets the `Topology` of the puzzle, an open line being drawn from an Eulerian path instead of a cycle.
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Returns the seed of the random number generator, if any.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Returns the `ClassificationScheme` the target class belongs to.
+    pub fn scheme(&self) -> &ClassificationScheme {
+        &self.scheme
+    }
+
+    /// Returns the `Topology` of the generated puzzles.
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Generates a puzzle, drawing the random choices from the seed if one is set.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Puzzle)` - A `Puzzle` instance with `Some(Tile)` values for placed tiles and `None` for removed tiles.
+    /// * `Err(DominoError::InvalidClass)` - If the target class does not exist in the scheme.
+    /// * `Err(DominoError::InvalidLength)` - If the solution set has not the length of a puzzle of size `n`.
+    /// * `Err(DominoError::GenerationError)` - If the options are inconsistent, the solution set is not a domino
+    ///   chain of the topology, the template does not fit the puzzle or the restarts allowed by the budget run out.
+    /// * `Err(DominoError::Timeout)` - If the time allowed by the budget runs out.
+    pub fn generate(&self) -> Result<Puzzle, DominoError> {
+        match self.seed {
+            Some(seed) => self.generate_with_rng(&mut StdRng::seed_from_u64(seed)),
+            None => self.generate_with_rng(&mut rand::thread_rng()),
+        }
+    }
+
+    /// Generates a puzzle drawing every random choice, from the Eulerian cycle to the removed tiles, from `rng`.
+    ///
+    /// The seed of the generator, if any, is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator, a seeded one makes the generation reproducible.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Puzzle)` - The generated puzzle.
+    /// * `Err(DominoError)` - If an error occurs, as for `generate`.
+    pub fn generate_with_rng<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Puzzle, DominoError> {
+        let expected_complexity = self
+            .class
+            .map(|class| ComplexityClass::with_scheme(class, &self.scheme))
+            .transpose()?;
+        self.check_options()?;
+        let mask = self
+            .template
+            .as_ref()
+            .map(|template| self.check_template(template, expected_complexity))
+            .transpose()?;
+
+        let start = Instant::now();
+        let graph = Graph::with_topology(self.n, self.topology);
+        let mut restarts: usize = 0;
+        loop {
+            let solution = match &self.solution {
+                Some(solution) => solution.clone(),
+                None => create_solution_from_cycle(&match self.topology {
+                    Topology::Closed => find_eulerian_cycle_with_rng(&graph, true, rng),
+                    Topology::Open => find_eulerian_path_with_rng(&graph, true, rng),
+                }),
+            };
+            let puzzle = match &mask {
+                Some(mask) => self.apply_template(&solution, mask, start)?,
+                None => self.remove_tiles(&solution, expected_complexity, start, rng)?,
+            };
+            if let Some(puzzle) = puzzle {
+                return Ok(puzzle.into());
+            }
+            if mask.is_some() && self.solution.is_some() {
+                return Err(DominoError::GenerationError(
+                    "the template does not lead to a unique puzzle for the solution".to_string(),
+                ));
+            }
+
+            // The solution is a dead end, restart from another one
+            restarts += 1;
+            if self.budget.max_restarts.is_some_and(|max_restarts| restarts > max_restarts) {
+                return Err(DominoError::GenerationError(format!(
+                    "the targets were not reached for n = {} within {} restarts",
+                    self.n,
+                    restarts - 1
+                )));
+            }
+        }
+    }
+
+    /// Returns an error if the options cannot be met by any puzzle.
+    fn check_options(&self) -> Result<(), DominoError> {
+        if self.class.is_none() && self.holes.is_none() && self.template.is_none() {
+            return Err(DominoError::GenerationError(
+                "either a target class, a number of holes or a template is required".to_string(),
+            ));
+        }
+        if self.template.is_some()
+            && (self.holes.is_some() || self.min_hole_length != 1 || self.max_hole_length.is_some())
+        {
+            return Err(DominoError::GenerationError(
+                "the template already sets the number and lengths of holes".to_string(),
+            ));
+        }
+        if self.holes == Some(0) {
+            return Err(DominoError::GenerationError(
+                "a puzzle needs at least one hole".to_string(),
+            ));
+        }
+        if self.min_hole_length == 0
+            || self
+                .max_hole_length
+                .is_some_and(|max_hole_length| max_hole_length < self.min_hole_length)
+        {
+            return Err(DominoError::GenerationError(format!(
+                "the hole length must be in [{}, {}], an empty range",
+                self.min_hole_length,
+                self.max_hole_length.map_or("inf".to_string(), |max| max.to_string())
+            )));
+        }
+        if let Some(solution) = &self.solution {
+            check_solution_chain(solution, self.n, self.topology)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the mask of the template, checking that it meets the target class.
+    fn check_template(
+        &self,
+        template: &HoleTemplate,
+        expected_complexity: Option<ComplexityClass>,
+    ) -> Result<Vec<bool>, DominoError> {
+        let mask = template.mask(self.topology.puzzle_length(self.n))?;
+
+        // The class only depends on the holes, so the class of the template is the class of every puzzle
+        if let Some(expected_complexity) = expected_complexity {
+            let actual_complexity =
+                classify_puzzle_with_topology(&template_layout(&mask), &self.scheme, self.topology)?;
+            if actual_complexity != expected_complexity {
+                return Err(DominoError::GenerationError(format!(
+                    "the template is of class {}, not of the target class {}",
+                    actual_complexity.0, expected_complexity.0
+                )));
+            }
+        }
+
+        Ok(mask)
+    }
+
+    /// Empties the positions of the mask in the solution.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(puzzle))` - The tiles of the puzzle, if it is unique and keeps the anchors and doubles.
+    /// * `Ok(None)` - If the solution does not fit the template.
+    /// * `Err(DominoError::Timeout)` - If the time allowed by the budget runs out.
+    fn apply_template(
+        &self,
+        solution: &Solution,
+        mask: &[bool],
+        start: Instant,
+    ) -> Result<Option<Vec<Option<Tile>>>, DominoError> {
+        if self
+            .budget
+            .timeout
+            .is_some_and(|timeout| start.elapsed() > timeout)
+        {
+            return Err(DominoError::Timeout);
+        }
+
+        let puzzle: Vec<Option<Tile>> = solution
+            .iter()
+            .zip(mask)
+            .map(|(&tile, &empty)| (!empty).then_some(tile))
+            .collect();
+        let keeps_options = solution.iter().zip(mask).all(|(tile, &empty)| {
+            !empty || (!self.anchors.contains(tile) && (self.allow_doubles || tile.0 != tile.1))
+        });
+        if !keeps_options || solve_all_with_topology(&puzzle.clone().into(), 2, self.topology)?.count() != 1 {
+            return Ok(None);
+        }
+
+        match validate_puzzle_with_topology(&puzzle.clone().into(), solution, self.topology) {
+            Ok(()) => Ok(Some(puzzle)),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Removes tiles from the solution until the targets are met.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(puzzle))` - The tiles of the puzzle meeting the targets.
+    /// * `Ok(None)` - If the targets were not met within the attempts allowed on the solution.
+    /// * `Err(DominoError::Timeout)` - If the time allowed by the budget runs out.
+    fn remove_tiles<R: Rng + ?Sized>(
+        &self,
+        solution: &Solution,
+        expected_complexity: Option<ComplexityClass>,
+        start: Instant,
+        rng: &mut R,
+    ) -> Result<Option<Vec<Option<Tile>>>, DominoError> {
+        let mut puzzle: Vec<Option<Tile>> = solution.iter().copied().map(Some).collect();
+        let len = puzzle.len();
+
+        // Attempts, counted rather than timed so that a seeded generation is reproducible.
+        // Reaching a class takes few removals, while a number of holes may require to empty most of the puzzle
+        let max_attempts = match expected_complexity {
+            Some(_) => (self.n + 3).max(MIN_ATTEMPTS_BEFORE_RESTART),
+            None => len.max(MIN_ATTEMPTS_BEFORE_RESTART),
+        };
+        let mut cursor = rng.gen_range(0..len);
+
+        for _ in 0..max_attempts {
+            if self
+                .budget
+                .timeout
+                .is_some_and(|timeout| start.elapsed() > timeout)
+            {
+                return Err(DominoError::Timeout);
+            }
+
+            let Some(position) = self.next_removal(&puzzle, &mut cursor, rng) else {
+                return Ok(None);
+            };
+            let removed_tile = puzzle[position].take();
+
+            // Removing more tiles can only increase the complexity, rollback the removals overshooting the class.
+            // A puzzle too simple to be classified is below the first class, so more tiles are removed
+            if let Some(expected_complexity) = expected_complexity {
+                let is_too_complex =
+                    classify_puzzle_with_topology(&puzzle.clone().into(), &self.scheme, self.topology)
+                        .is_ok_and(|actual_complexity| actual_complexity > expected_complexity);
+                if is_too_complex {
+                    puzzle[position] = removed_tile;
+                    continue;
+                }
+            }
+
+            // Removing more tiles never makes an ambiguous puzzle unique again, rollback the removal.
+            // The backtracking search is much faster than the model, which only confirms the final puzzle
+            if solve_all_with_topology(&puzzle.clone().into(), 2, self.topology)?.count() != 1 {
+                puzzle[position] = removed_tile;
+                continue;
+            }
+
+            if self.meets_targets(&puzzle, expected_complexity) {
+                return match validate_puzzle_with_topology(&puzzle.clone().into(), solution, self.topology) {
+                    Ok(()) => Ok(Some(puzzle)),
+                    Err(_) => Ok(None),
+                };
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the position of the next tile to remove, or `None` if no tile can be removed.
+    fn next_removal<R: Rng + ?Sized>(
+        &self,
+        puzzle: &[Option<Tile>],
+        cursor: &mut usize,
+        rng: &mut R,
+    ) -> Option<usize> {
+        let len = puzzle.len();
+        if self.random {
+            // Favour the tiles following a hole, extending the holes rather than opening new ones
+            *cursor = rng.gen_range(0..len);
+            for _ in 0..HOLE_EXTENSION_DRAWS {
+                if puzzle[*cursor].is_some() {
+                    *cursor = rng.gen_range(0..len);
+                }
+            }
+        }
+        let position = (0..len)
+            .map(|offset| (*cursor + offset) % len)
+            .find(|&position| self.is_removable(puzzle, position))?;
+        *cursor = (position + 1) % len;
+        Some(position)
+    }
+
+    /// Returns whether the tile at `position` can be removed without breaking the options.
+    fn is_removable(&self, puzzle: &[Option<Tile>], position: usize) -> bool {
+        let Some(tile) = puzzle[position] else {
+            return false;
+        };
+        if self.anchors.contains(&tile) || (!self.allow_doubles && tile.0 == tile.1) {
+            return false;
+        }
+
+        self.max_hole_length.is_none_or(|max_hole_length| {
+            let len = puzzle.len();
+            let empty_run = |step: fn(Topology, usize, usize) -> Option<usize>| {
+                let mut current = position;
+                (1..len)
+                    .map_while(|_| {
+                        current = step(self.topology, current, len)?;
+                        puzzle[current].is_none().then_some(())
+                    })
+                    .count()
+            };
+            let before = empty_run(Topology::previous);
+            let after = empty_run(Topology::next);
+            (before + 1 + after).min(len) <= max_hole_length
+        })
+    }
+
+    /// Returns whether the puzzle, already known to be valid, meets the targets.
+    fn meets_targets(
+        &self,
+        puzzle: &[Option<Tile>],
+        expected_complexity: Option<ComplexityClass>,
+    ) -> bool {
+        let hole_lengths = hole_lengths(puzzle, self.topology);
+        if self.holes.is_some_and(|holes| holes != hole_lengths.len())
+            || hole_lengths
+                .iter()
+                .any(|&hole_length| hole_length < self.min_hole_length)
+        {
+            return false;
+        }
+
+        expected_complexity.is_none_or(|expected_complexity| {
+            classify_puzzle_with_topology(&puzzle.to_vec().into(), &self.scheme, self.topology)
+                .is_ok_and(|actual_complexity| actual_complexity == expected_complexity)
+        })
+    }
+}
+
+/// Returns the lengths of the runs of consecutive empty positions, a run of a closed puzzle possibly wrapping
+/// around the end.
+///
+/// # Arguments
+///
+/// * `puzzle` - The tiles of the puzzle.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// The length of each hole of the puzzle.
+pub(super) fn hole_lengths(puzzle: &[Option<Tile>], topology: Topology) -> Vec<usize> {
+    if topology == Topology::Open {
+        return puzzle
+            .split(|tile| tile.is_some())
+            .map(|run| run.len())
+            .filter(|&length| length > 0)
+            .collect();
+    }
+
+    let len = puzzle.len();
+    let Some(first_tile) = puzzle.iter().position(|tile| tile.is_some()) else {
+        return if len == 0 { vec![] } else { vec![len] };
+    };
+
+    // Start scanning from a placed tile so that a hole wrapping around the end is seen as a single run
+    let mut lengths = Vec::new();
+    let mut current = 0;
+    for offset in 1..=len {
+        if puzzle[(first_tile + offset) % len].is_none() {
+            current += 1;
+        } else if current > 0 {
+            lengths.push(current);
+            current = 0;
+        }
+    }
+    lengths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hole_lengths, PuzzleGenerator};
+    use crate::{
+        classify_puzzle, classify_puzzle_with_topology, solve_all_with_topology, ClassificationScheme,
+        ComplexityClass, DominoError, HoleTemplate, Tile, Topology,
+    };
+
+    #[test]
+    fn test_generator_hole_lengths() {
+        let tile = Some(Tile(0, 0));
+        let closed = Topology::Closed;
+        assert_eq!(hole_lengths(&[tile, None, None, tile, None], closed), vec![2, 1]);
+        assert_eq!(hole_lengths(&[None, tile, None, tile, None], closed), vec![1, 2]);
+        assert_eq!(hole_lengths(&[None, None], closed), vec![2]);
+        assert!(hole_lengths(&[tile, tile], closed).is_empty());
+        assert_eq!(hole_lengths(&[None, tile, None, tile, None], Topology::Open), vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_generator_class() {
+        let puzzle = PuzzleGenerator::new(3).with_class(2).generate().unwrap();
+        assert_eq!(classify_puzzle(&puzzle), Ok(ComplexityClass(2)));
+    }
+
+    #[test]
+    fn test_generator_seed() {
+        let generator = PuzzleGenerator::new(3).with_class(1).with_seed(7).with_random(false);
+        assert_eq!(generator.generate().unwrap().0, generator.generate().unwrap().0);
+    }
+
+    #[test]
+    fn test_generator_holes() {
+        let puzzle = PuzzleGenerator::new(3)
+            .with_holes(2)
+            .with_max_hole_length(2)
+            .generate()
+            .unwrap();
+        let lengths = hole_lengths(&puzzle.0, Topology::Closed);
+        assert_eq!(lengths.len(), 2);
+        assert!(lengths.iter().all(|&length| length <= 2));
+    }
+
+    #[test]
+    fn test_generator_anchors_and_doubles() {
+        let anchor = Tile(1, 2);
+        let puzzle = PuzzleGenerator::new(3)
+            .with_holes(2)
+            .with_anchor(anchor)
+            .with_doubles(false)
+            .generate()
+            .unwrap();
+        assert!(puzzle.0.contains(&Some(anchor)));
+        let doubles = puzzle
+            .0
+            .iter()
+            .filter(|tile| matches!(tile, Some(Tile(left, right)) if left == right))
+            .count();
+        assert_eq!(doubles, 4);
+    }
+
+    #[test]
+    fn test_generator_min_hole_length() {
+        let puzzle = PuzzleGenerator::new(3)
+            .with_holes(1)
+            .with_min_hole_length(3)
+            .with_random(false)
+            .generate()
+            .unwrap();
+        assert!(hole_lengths(&puzzle.0, Topology::Closed).iter().all(|&length| length >= 3));
+    }
+
+    #[test]
+    fn test_generator_inconsistent_options() {
+        assert!(matches!(
+            PuzzleGenerator::new(3).generate(),
+            Err(DominoError::GenerationError(_))
+        ));
+        assert!(matches!(
+            PuzzleGenerator::new(3)
+                .with_holes(1)
+                .with_min_hole_length(3)
+                .with_max_hole_length(2)
+                .generate(),
+            Err(DominoError::GenerationError(_))
+        ));
+        assert!(matches!(
+            PuzzleGenerator::new(3).with_class(0).generate(),
+            Err(DominoError::InvalidClass(_))
+        ));
+    }
+
+    #[test]
+    fn test_generator_template() {
+        let template = HoleTemplate::EvenlySpaced { count: 3, length: 2 };
+        let puzzle = PuzzleGenerator::new(4)
+            .with_template(template.clone())
+            .with_seed(3)
+            .generate()
+            .unwrap();
+        assert_eq!(HoleTemplate::from_puzzle(&puzzle).mask(15), template.mask(15));
+
+        let class = classify_puzzle(&puzzle).unwrap();
+        let other_class = if class.0 == 1 { 2 } else { 1 };
+        assert!(matches!(
+            PuzzleGenerator::new(4)
+                .with_template(template.clone())
+                .with_class(other_class)
+                .generate(),
+            Err(DominoError::GenerationError(_))
+        ));
+        assert!(matches!(
+            PuzzleGenerator::new(4).with_template(template).with_holes(3).generate(),
+            Err(DominoError::GenerationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_generator_open_topology() {
+        let generator = PuzzleGenerator::new(5)
+            .with_topology(Topology::Open)
+            .with_class(2)
+            .with_seed(4);
+        let puzzle = generator.generate().unwrap();
+        assert_eq!(puzzle.len(), Topology::Open.puzzle_length(5));
+        assert_eq!(
+            classify_puzzle_with_topology(&puzzle, &ClassificationScheme::default(), Topology::Open),
+            Ok(ComplexityClass(2))
+        );
+        let solutions: Vec<_> = solve_all_with_topology(&puzzle, 2, Topology::Open).unwrap().collect();
+        assert_eq!(solutions.len(), 1);
+        // The free ends of an open line of odd size hold different values
+        assert_ne!(solutions[0][0].0, solutions[0][puzzle.len() - 1].1);
+    }
+}
@@ -0,0 +1,234 @@
+//! This module generates puzzles with as few given tiles as possible while keeping them uniquely solvable.
+//!
+//! Removing a tile never makes an ambiguous puzzle unique again, so removing the tiles of a solution one
+//! after the other, and keeping only the ones whose removal makes the puzzle ambiguous, leads to a puzzle
+//! where every given is necessary. Uniqueness is checked with the backtracking search.
+
+use std::time::Instant;
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use super::{create_solution_from_cycle, GenerationBudget};
+use crate::{
+    solve_all, utils::find_eulerian_cycle_with_rng, DominoError, Graph, Puzzle, Solution, Tile,
+};
+
+/// Selects how minimal the set of given tiles of a puzzle has to be.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Minimality {
+    /// No given can be removed without making the puzzle ambiguous.
+    #[default]
+    Local,
+    /// No puzzle with the same solution has fewer givens. The search is exponential in the number of tiles,
+    /// so it is only practical for small sizes.
+    Global,
+}
+
+/// A minimal puzzle, along with the data proving its minimality.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinimalPuzzle {
+    /// The generated puzzle.
+    pub puzzle: Puzzle,
+    /// The number of given tiles of the puzzle.
+    pub givens: usize,
+    /// Whether removing any given makes the puzzle ambiguous, checked on the generated puzzle.
+    pub every_given_necessary: bool,
+    /// Whether no puzzle with the same solution has fewer givens.
+    pub globally_minimal: bool,
+}
+
+/// Generates a locally minimal puzzle: every given tile is necessary for the puzzle to be unique.
+///
+/// # Arguments
+///
+/// * `n` - The size of the puzzle.
+/// * `seed` - The seed of the random number generator driving the generation.
+///
+/// # Returns
+///
+/// * `Ok(MinimalPuzzle)` - The puzzle with its number of givens and the proof of their necessity.
+/// * `Err(DominoError::InvalidLength)` - If the size does not lead to a valid puzzle.
+pub fn generate_minimal(n: usize, seed: u64) -> Result<MinimalPuzzle, DominoError> {
+    generate_minimal_with(n, seed, Minimality::Local, &GenerationBudget::unlimited())
+}
+
+/// Generates a minimal puzzle according to the given `Minimality`.
+///
+/// # Arguments
+///
+/// * `n` - The size of the puzzle.
+/// * `seed` - The seed of the random number generator driving the generation.
+/// * `minimality` - Whether the puzzle has to be locally or globally minimal.
+/// * `budget` - The `GenerationBudget` bounding the search, only its timeout is used.
+///
+/// # Returns
+///
+/// * `Ok(MinimalPuzzle)` - The puzzle with its number of givens and the proof of their necessity.
+/// * `Err(DominoError::InvalidLength)` - If the size does not lead to a valid puzzle.
+/// * `Err(DominoError::Timeout)` - If the time allowed by the budget runs out.
+pub fn generate_minimal_with(
+    n: usize,
+    seed: u64,
+    minimality: Minimality,
+    budget: &GenerationBudget,
+) -> Result<MinimalPuzzle, DominoError> {
+    let start = Instant::now();
+    let is_expired = || {
+        budget
+            .timeout()
+            .is_some_and(|timeout| start.elapsed() > timeout)
+    };
+    let mut rng = StdRng::seed_from_u64(seed);
+    let solution = create_solution_from_cycle(&find_eulerian_cycle_with_rng(
+        &Graph::regular(n),
+        true,
+        &mut rng,
+    ));
+
+    let mut puzzle: Vec<Option<Tile>> = solution.iter().copied().map(Some).collect();
+    let mut positions: Vec<usize> = (0..puzzle.len()).collect();
+    positions.shuffle(&mut rng);
+    for position in positions {
+        if is_expired() {
+            return Err(DominoError::Timeout);
+        }
+        let removed_tile = puzzle[position].take();
+        if !is_unique(&puzzle)? {
+            puzzle[position] = removed_tile;
+        }
+    }
+
+    let globally_minimal = match minimality {
+        Minimality::Local => false,
+        Minimality::Global => {
+            if let Some(smaller) = find_fewer_givens(&solution, count_givens(&puzzle), &is_expired)? {
+                puzzle = smaller;
+            }
+            true
+        }
+    };
+
+    Ok(MinimalPuzzle {
+        givens: count_givens(&puzzle),
+        every_given_necessary: every_given_necessary(&puzzle)?,
+        globally_minimal,
+        puzzle: puzzle.into(),
+    })
+}
+
+/// Searches the puzzle with the fewest givens, less than `givens`, whose only completion is the solution.
+///
+/// The sets of givens are enumerated by increasing size, so the first unique puzzle found is a minimum.
+///
+/// # Returns
+///
+/// * `Ok(Some(puzzle))` - A unique puzzle with the fewest givens.
+/// * `Ok(None)` - If no unique puzzle has less than `givens` givens.
+/// * `Err(DominoError::Timeout)` - If `is_expired` returns `true` during the search.
+fn find_fewer_givens(
+    solution: &Solution,
+    givens: usize,
+    is_expired: &dyn Fn() -> bool,
+) -> Result<Option<Vec<Option<Tile>>>, DominoError> {
+    let len = solution.len();
+    for size in 1..givens {
+        // The positions of the givens, as the first combination of `size` positions in lexicographic order
+        let mut combination: Vec<usize> = (0..size).collect();
+        loop {
+            if is_expired() {
+                return Err(DominoError::Timeout);
+            }
+            let mut puzzle: Vec<Option<Tile>> = vec![None; len];
+            for &position in &combination {
+                puzzle[position] = Some(solution[position]);
+            }
+            if is_unique(&puzzle)? {
+                return Ok(Some(puzzle));
+            }
+
+            // Advance to the next combination, or to the next size when they are exhausted
+            let Some(index) = (0..size).rev().find(|&index| combination[index] < len - size + index) else {
+                break;
+            };
+            combination[index] += 1;
+            for next in index + 1..size {
+                combination[next] = combination[next - 1] + 1;
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Returns whether removing any of the givens makes the puzzle ambiguous.
+fn every_given_necessary(puzzle: &[Option<Tile>]) -> Result<bool, DominoError> {
+    let mut puzzle = puzzle.to_vec();
+    for position in 0..puzzle.len() {
+        let Some(tile) = puzzle[position].take() else {
+            continue;
+        };
+        let is_necessary = !is_unique(&puzzle)?;
+        puzzle[position] = Some(tile);
+        if !is_necessary {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Returns whether the puzzle has exactly one completion.
+fn is_unique(puzzle: &[Option<Tile>]) -> Result<bool, DominoError> {
+    Ok(solve_all(&puzzle.to_vec().into(), 2)?.count() == 1)
+}
+
+/// Returns the number of tiles placed in the puzzle.
+fn count_givens(puzzle: &[Option<Tile>]) -> usize {
+    puzzle.iter().filter(|tile| tile.is_some()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_minimal, generate_minimal_with, Minimality};
+    use crate::{solve_all, DominoError, GenerationBudget};
+
+    #[test]
+    fn test_minimal_local() {
+        let minimal = generate_minimal(3, 42).unwrap();
+        assert!(minimal.every_given_necessary);
+        assert!(!minimal.globally_minimal);
+        assert_eq!(
+            minimal.givens,
+            minimal.puzzle.0.iter().filter(|tile| tile.is_some()).count()
+        );
+        assert_eq!(solve_all(&minimal.puzzle, 2).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_minimal_seeded() {
+        assert_eq!(
+            generate_minimal(6, 7).unwrap().puzzle.0,
+            generate_minimal(6, 7).unwrap().puzzle.0
+        );
+    }
+
+    #[test]
+    fn test_minimal_global() {
+        let local = generate_minimal(3, 1).unwrap();
+        let global =
+            generate_minimal_with(3, 1, Minimality::Global, &GenerationBudget::unlimited()).unwrap();
+        assert!(global.globally_minimal);
+        assert!(global.every_given_necessary);
+        assert!(global.givens <= local.givens);
+        assert_eq!(solve_all(&global.puzzle, 2).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_minimal_timeout() {
+        let budget = GenerationBudget::new(Some(std::time::Duration::ZERO), None);
+        assert_eq!(
+            generate_minimal_with(6, 1, Minimality::Global, &budget),
+            Err(DominoError::Timeout)
+        );
+    }
+}
@@ -1,130 +1,247 @@
 //! This module provides functionality for generating valid puzzle instances.
 //!
 //! It includes a function to generate a puzzle with a valid Eulerian cycle and remove a specified number of tiles.
+//! Generation is bounded by a `GenerationBudget`, so that unreachable targets are reported instead of looping forever,
+//! and every option of the generation is exposed by the `PuzzleGenerator` builder. Packs of distinct puzzles
+//! are generated in parallel by `generate_batch`, and puzzles with the fewest givens by `generate_minimal`.
+//! The layout of the holes can also be fixed beforehand with a `HoleTemplate`.
 
-use std::time::{Duration, Instant};
+mod batch;
+mod generator;
+mod minimal;
+mod template;
 
-use crate::{classify_puzzle, utils::find_eulerian_cycle, validate_puzzle, ComplexityClass, Graph, Puzzle, Solution, Tile};
-use rand::Rng;
+use std::{collections::HashSet, time::Duration};
+
+use crate::{
+    utils::get_n, ClassificationScheme, DominoError, Graph, Puzzle, Solution, Tile, Topology, Tournament,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+pub use batch::{generate_batch, generate_batch_with, GeneratedPuzzle};
+pub use generator::PuzzleGenerator;
+pub use minimal::{generate_minimal, generate_minimal_with, MinimalPuzzle, Minimality};
+pub use template::HoleTemplate;
+
+/// The time allowed to the generation by the default `GenerationBudget`.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+/// The number of restarts allowed to the generation by the default `GenerationBudget`.
+const DEFAULT_MAX_RESTARTS: usize = 10_000;
+
+/// Bounds the resources spent generating a puzzle before giving up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenerationBudget {
+    timeout: Option<Duration>,
+    max_restarts: Option<usize>,
+}
+
+impl GenerationBudget {
+    /// Creates a new `GenerationBudget`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The time allowed to the generation, `None` for no time limit.
+    /// * `max_restarts` - The number of restarts from a new solution allowed, `None` for no limit.
+    ///
+    /// # Returns
+    ///
+    /// The `GenerationBudget` with the given limits.
+    pub fn new(timeout: Option<Duration>, max_restarts: Option<usize>) -> GenerationBudget {
+        Self {
+            timeout,
+            max_restarts,
+        }
+    }
+
+    /// Returns a `GenerationBudget` without limits, the generation looping until the target is met.
+    pub fn unlimited() -> GenerationBudget {
+        Self::new(None, None)
+    }
+
+    /// Returns the time allowed to the generation, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Returns the number of restarts allowed to the generation, if any.
+    pub fn max_restarts(&self) -> Option<usize> {
+        self.max_restarts
+    }
+}
+
+impl Default for GenerationBudget {
+    /// Returns the budget allowing `DEFAULT_TIMEOUT` and `DEFAULT_MAX_RESTARTS` restarts.
+    fn default() -> Self {
+        Self::new(Some(DEFAULT_TIMEOUT), Some(DEFAULT_MAX_RESTARTS))
+    }
+}
 
 /// Generates a puzzle with a valid Eulerian cycle and removes a specified number of tiles.
 ///
 /// This function constructs a `Graph` representation of the puzzle, finds an Eulerian cycle,
-/// and converts the cycle into a `Solution`. Then, it removes a specified number of tiles
-/// either sequentially or randomly, based on the `random` flag.
+/// and converts the cycle into a `Solution`. Then, it removes tiles at random until the puzzle
+/// reaches the class `c`; see `PuzzleGenerator` for the other options.
+///
+/// The generation is bounded by the default `GenerationBudget`.
 ///
 /// # Arguments
 ///
 /// * `n` - The size of the puzzle.
-/// * `minimum_removals` - The number of tiles to remove from the solution.
-/// * `random` - If `true`, removes tiles at random; otherwise, removes them sequentially.
+/// * `c` - The target complexity class.
 ///
 /// # Returns
 ///
-/// A `Puzzle` instance with `Some(Tile)` values for placed tiles and `None` for removed tiles.
-pub fn generate_puzzle(n: usize, c: usize) -> Puzzle {
-    let graph = Graph::regular(n);
-    let eulerian_cycle = find_eulerian_cycle(&graph,true);
-    let mut solution: Solution = create_solution_from_cycle(&eulerian_cycle);
-    let mut puzzle= solution.clone().into_iter().map(Some).collect::<Vec<Option<Tile>>>();
-    let mut rng = rand::thread_rng();
-
-    // Complexity checks
-    let mut expected_complexity = ComplexityClass::new(c).ok();
-    let mut actual_complexity: Option<ComplexityClass> = None;
-    let mut is_not_complex_enough = actual_complexity != expected_complexity;
-    let mut is_too_complex = false;
-
-    // Timeout
-    let mut now = Instant::now();
-    let timeout = Duration::from_millis(2000);
-
-    // Validity checks
-    let is_not_valid = validate_puzzle(&puzzle.clone().into(), &solution).is_err();
-
-    // Removal history
-    let mut index = rng.gen_range(0..puzzle.len());
-    let mut removal_history: Vec<(Option<Tile>, usize)> = vec![(puzzle[index].clone(), index)];
-    puzzle[index] = None;
-
-
-    // Remove tiles
-    while is_not_valid || is_not_complex_enough {
-
-      while is_too_complex {
-        // println!("is_too_complex");
-        reinsert_tile(&mut puzzle, &mut removal_history);
-        update_complexity(&mut actual_complexity, &mut expected_complexity, &puzzle, &mut is_not_complex_enough, &mut is_too_complex);
-      }
-
-      let removed_tile: Option<Tile>;
-      let removed_position: Option<usize>;
-
-      // Remove a tile at a true position
-      (puzzle, removed_tile, removed_position) = remove_non_empty_tile(puzzle);
-      removal_history.push((removed_tile, removed_position.unwrap()));
-
-      // Update complexity checks
-      update_complexity(&mut actual_complexity, &mut expected_complexity, &puzzle, &mut is_not_complex_enough, &mut is_too_complex);
-
-      // Update validity checks
-      let is_not_valid = validate_puzzle(&puzzle.clone().into(), &solution).is_err();
-
-      // The puzzle becomes invalid rollback
-      if is_not_valid {
-        reinsert_tile(&mut puzzle, &mut removal_history);
-        update_complexity(&mut actual_complexity, &mut expected_complexity, &puzzle, &mut is_not_complex_enough, &mut is_too_complex);
-      }
-
-      // If the time spent trying to reach the desired complexity is greater than the timeout then restart with another initial solution
-      if now.elapsed().as_millis() > timeout.as_millis() || actual_complexity.is_none(){
-        // println!("timeout, puzzle is: {puzzle:?}");
-        solution = create_solution_from_cycle(&eulerian_cycle);
-        puzzle = solution.clone().into_iter().map(Some).collect::<Vec<Option<Tile>>>().into();
-        index = rng.gen_range(0..puzzle.len());
-        removal_history = vec![(puzzle[index].clone(), index)];
-        puzzle[index] = None;
-        update_complexity(&mut actual_complexity, &mut expected_complexity, &puzzle, &mut is_not_complex_enough, &mut is_too_complex);
-        now = Instant::now();
-      }
-
-    }
+/// * `Ok(Puzzle)` - A `Puzzle` instance with `Some(Tile)` values for placed tiles and `None` for removed tiles.
+/// * `Err(DominoError::InvalidClass)` - If the class `c` does not exist.
+/// * `Err(DominoError::Timeout)` - If the time allowed by the budget runs out.
+/// * `Err(DominoError::GenerationError)` - If the restarts allowed by the budget run out.
+pub fn generate_puzzle(n: usize, c: usize) -> Result<Puzzle, DominoError> {
+    generate_puzzle_with(n, c, &ClassificationScheme::default())
+}
 
-    puzzle.into()
+/// Generates a puzzle of class `c` within the given `GenerationBudget`.
+///
+/// # Arguments
+///
+/// * `n` - The size of the puzzle.
+/// * `c` - The target complexity class.
+/// * `budget` - The `GenerationBudget` bounding the generation.
+///
+/// # Returns
+///
+/// * `Ok(Puzzle)` - The generated puzzle.
+/// * `Err(DominoError)` - If an error occurs, as for `generate_puzzle`.
+pub fn generate_puzzle_with_budget(n: usize, c: usize, budget: &GenerationBudget) -> Result<Puzzle, DominoError> {
+    generate_puzzle_with_rng(n, c, &ClassificationScheme::default(), budget, &mut rand::thread_rng())
 }
 
-fn update_complexity(actual_complexity: &mut Option<ComplexityClass>, expected_complexity: &mut Option<ComplexityClass>, puzzle: &Vec<Option<Tile>>, is_not_complex_enough: &mut bool, is_too_complex: &mut bool) {
-  let result = classify_puzzle(&puzzle.clone().into());
-  *actual_complexity = result.ok();
-  *is_not_complex_enough = actual_complexity != expected_complexity;
-  *is_too_complex = actual_complexity.is_some() && actual_complexity.unwrap() > expected_complexity.unwrap();
+/// Generates a puzzle whose complexity is the class `c` of the given `ClassificationScheme`.
+///
+/// # Arguments
+///
+/// * `n` - The size of the puzzle.
+/// * `c` - The target complexity class, in `[1, scheme.number_of_classes()]`.
+/// * `scheme` - The `ClassificationScheme` used to classify the puzzle while removing tiles.
+///
+/// # Returns
+///
+/// * `Ok(Puzzle)` - The generated puzzle.
+/// * `Err(DominoError)` - If an error occurs, as for `generate_puzzle`.
+pub fn generate_puzzle_with(n: usize, c: usize, scheme: &ClassificationScheme) -> Result<Puzzle, DominoError> {
+    generate_puzzle_with_rng(n, c, scheme, &GenerationBudget::default(), &mut rand::thread_rng())
+}
 
+/// Generates a reproducible puzzle: the same `(n, c, seed)` always yields the same `Puzzle`.
+///
+/// # Arguments
+///
+/// * `n` - The size of the puzzle.
+/// * `c` - The target complexity class.
+/// * `seed` - The seed of the random number generator driving the generation.
+///
+/// # Returns
+///
+/// * `Ok(Puzzle)` - The generated puzzle.
+/// * `Err(DominoError)` - If an error occurs, as for `generate_puzzle`.
+pub fn generate_puzzle_from_seed(n: usize, c: usize, seed: u64) -> Result<Puzzle, DominoError> {
+    generate_puzzle_with_rng(n, c, &ClassificationScheme::default(), &GenerationBudget::default(), &mut StdRng::seed_from_u64(seed))
 }
 
-fn reinsert_tile(puzzle: &mut Vec<Option<Tile>>, history: &mut Vec<(Option<Tile>, usize)>) {
-  // println!("Puzzle is not valid reinserting tile");
-  let (removed_tile, removed_position) = history.pop().unwrap();
-  puzzle[removed_position] = removed_tile;
-  // println!("puzzle: {:?}, removed_tile: {:?}, removed_position: {:?}", puzzle, removed_tile, removed_position);
+/// Generates a puzzle drawing every random choice, from the Eulerian cycle to the removed tiles, from `rng`.
+///
+/// # Arguments
+///
+/// * `n` - The size of the puzzle.
+/// * `c` - The target complexity class, in `[1, scheme.number_of_classes()]`.
+/// * `scheme` - The `ClassificationScheme` used to classify the puzzle while removing tiles.
+/// * `budget` - The `GenerationBudget` bounding the generation.
+/// * `rng` - The random number generator, a seeded one makes the generation reproducible.
+///
+/// # Returns
+///
+/// * `Ok(Puzzle)` - The generated puzzle.
+/// * `Err(DominoError)` - If an error occurs, as for `generate_puzzle`.
+pub fn generate_puzzle_with_rng<R: Rng + ?Sized>(n: usize, c: usize, scheme: &ClassificationScheme, budget: &GenerationBudget, rng: &mut R) -> Result<Puzzle, DominoError> {
+    PuzzleGenerator::new(n)
+        .with_class(c)
+        .with_scheme(scheme.clone())
+        .with_budget(*budget)
+        .generate_with_rng(rng)
+}
 
+/// Generates a puzzle of class `c` by removing tiles from the given solution.
+///
+/// The size of the puzzle is derived from the length of the solution, which must be a closed domino cycle
+/// using every tile of the tileset exactly once.
+///
+/// # Arguments
+///
+/// * `solution` - The solution the puzzle is carved out of, e.g. a themed sequence.
+/// * `c` - The target complexity class.
+///
+/// # Returns
+///
+/// * `Ok(Puzzle)` - A `Puzzle` whose only completion is `solution`.
+/// * `Err(DominoError::InvalidLength)` - If the solution has not the length of a puzzle.
+/// * `Err(DominoError::GenerationError)` - If the solution is not a closed domino cycle, or the class cannot be reached.
+/// * `Err(DominoError)` - If another error occurs, as for `generate_puzzle`.
+pub fn generate_from_solution(solution: &Solution, c: usize) -> Result<Puzzle, DominoError> {
+    let n = get_n(&solution.iter().copied().map(Some).collect::<Vec<Option<Tile>>>().into())? as usize;
+    PuzzleGenerator::new(n)
+        .with_solution(solution.clone())
+        .with_class(c)
+        .generate()
 }
 
-fn remove_non_empty_tile(mut puzzle: Vec<Option<Tile>>) -> (Vec<Option<Tile>>, Option<Tile>, Option<usize>) {
-  let mut rng = rand::thread_rng();
-  let mut index = rng.gen_range(0..puzzle.len());
+/// Checks that the solution is a domino chain of the given topology made of the whole tileset of size `n`.
+///
+/// # Arguments
+///
+/// * `solution` - The solution to check.
+/// * `n` - The size of the puzzle.
+/// * `topology` - The `Topology` of the chain: a closed cycle or an open line.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the solution is a legal domino chain.
+/// * `Err(DominoError::InvalidLength)` - If the solution has not the length of a puzzle of size `n`.
+/// * `Err(DominoError::GenerationError)` - If the tiles do not chain, are repeated or do not belong to the tileset.
+fn check_solution_chain(solution: &Solution, n: usize, topology: Topology) -> Result<(), DominoError> {
+    if solution.len() != topology.puzzle_length(n) {
+        return Err(DominoError::InvalidLength);
+    }
+    let chain = match topology {
+        Topology::Closed => "closed domino cycle",
+        Topology::Open => "open domino chain",
+    };
+    let invalid = |reason: String| DominoError::GenerationError(format!("the solution is not a {}, {}", chain, reason));
+
+    let len = solution.len();
+    let next = |position: usize| topology.next(position, len).map(|next| solution[next]);
+    if let Some(position) = (0..len).find(|&position| next(position).is_some_and(|next| solution[position].1 != next.0)) {
+        return Err(invalid(format!(
+            "{} at position {} is followed by {}",
+            solution[position], position, solution[(position + 1) % len]
+        )));
+    }
+    Tournament::with_topology(solution.clone(), topology).map_err(invalid)?;
 
-  for _ in 0..10 {
-    if puzzle[index].is_some() {
-      index = rng.gen_range(0..puzzle.len());
+    let graph = Graph::with_topology(n, topology);
+    let mut used: HashSet<Tile> = HashSet::new();
+    for tile in solution {
+        let in_tileset = graph
+            .adjacency
+            .get(&tile.0)
+            .is_some_and(|arcs| arcs.iter().any(|arc| arc.destination == tile.1));
+        if !in_tileset {
+            return Err(invalid(format!("{} is not a tile of size {}", tile, n)));
+        }
+        if !used.insert(*tile) {
+            return Err(invalid(format!("{} is used more than once", tile)));
+        }
     }
-  }
-
-  while puzzle[index].is_none() {
-      index = (index + 1) % puzzle.len();
-  }
-  let removed_tile = puzzle[index].clone();
-  puzzle[index] = None;
-  (puzzle.clone(), removed_tile, Some(index))
+
+    Ok(())
 }
 
 fn create_solution_from_cycle(eulerian_cycle: &Vec<crate::Node>) -> Solution {
@@ -138,3 +255,92 @@ eulerian_cycle
     })
     .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{generate_from_solution, generate_puzzle, generate_puzzle_with_budget, GenerationBudget};
+    use crate::{solve_all, DominoError, Solution, Tile};
+
+    fn solution() -> Solution {
+        vec![
+            Tile(0, 0),
+            Tile(0, 1),
+            Tile(1, 1),
+            Tile(1, 2),
+            Tile(2, 2),
+            Tile(2, 3),
+            Tile(3, 3),
+            Tile(3, 0),
+        ]
+    }
+
+    #[test]
+    fn test_generate_invalid_class() {
+        assert!(matches!(
+            generate_puzzle(3, 0),
+            Err(DominoError::InvalidClass(_))
+        ));
+    }
+
+    #[test]
+    fn test_generate_restarts_exhausted() {
+        // The only tile of the puzzle cannot be removed without leaving it empty
+        let budget = GenerationBudget::new(None, Some(10));
+        assert!(matches!(
+            generate_puzzle_with_budget(0, 1, &budget),
+            Err(DominoError::GenerationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_generate_timeout() {
+        let budget = GenerationBudget::new(Some(Duration::ZERO), None);
+        assert_eq!(
+            generate_puzzle_with_budget(0, 1, &budget),
+            Err(DominoError::Timeout)
+        );
+    }
+
+    #[test]
+    fn test_generate_within_budget() {
+        let puzzle = generate_puzzle_with_budget(3, 1, &GenerationBudget::default()).unwrap();
+        assert_eq!(puzzle.len(), 8);
+    }
+
+    #[test]
+    fn test_generate_from_solution() {
+        let puzzle = generate_from_solution(&solution(), 1).unwrap();
+        let completions: Vec<Solution> = solve_all(&puzzle, 2).unwrap().collect();
+        assert_eq!(completions.len(), 1);
+        assert!(completions[0]
+            .iter()
+            .zip(solution())
+            .all(|(completion, tile)| (completion.0, completion.1) == (tile.0, tile.1)));
+    }
+
+    #[test]
+    fn test_generate_from_invalid_solution() {
+        let mut unchained = solution();
+        unchained.swap(1, 3);
+        assert!(matches!(
+            generate_from_solution(&unchained, 1),
+            Err(DominoError::GenerationError(_))
+        ));
+
+        // [0,2] is not a tile of size 3, and [1,1] is replaced by a second [1,2]
+        let mut outside = solution();
+        outside[1] = Tile(0, 2);
+        outside[2] = Tile(2, 1);
+        assert!(matches!(
+            generate_from_solution(&outside, 1),
+            Err(DominoError::GenerationError(_))
+        ));
+
+        assert_eq!(
+            generate_from_solution(&solution()[..7].to_vec(), 1),
+            Err(DominoError::InvalidLength)
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! This module defines the `HoleTemplate`, the layout of the holes a generated puzzle must have.
+//!
+//! A template fixes which positions of the puzzle are empty, the generator then searches the solutions whose
+//! tiles at the other positions make the puzzle unique.
+
+use crate::{classify::detect_holes, DominoError, Puzzle, Tile};
+
+/// The layout of the empty positions of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HoleTemplate {
+    /// An explicit mask, `true` marking an empty position. It has the length of the puzzle.
+    Mask(Vec<bool>),
+    /// `count` holes of `length` consecutive empty positions, spread evenly along the cycle.
+    EvenlySpaced { count: usize, length: usize },
+}
+
+impl HoleTemplate {
+    /// Creates the template of the empty positions of a puzzle.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle` - A reference to the `Puzzle` whose holes are copied.
+    ///
+    /// # Returns
+    ///
+    /// The `HoleTemplate::Mask` of the puzzle.
+    pub fn from_puzzle(puzzle: &Puzzle) -> HoleTemplate {
+        HoleTemplate::Mask(puzzle.0.iter().map(|tile| tile.is_none()).collect())
+    }
+
+    /// Returns the mask of the template for a puzzle of length `len`.
+    ///
+    /// # Arguments
+    ///
+    /// * `len` - The length of the puzzle.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<bool>)` - The mask, `true` marking an empty position.
+    /// * `Err(DominoError::InvalidLength)` - If an explicit mask has not the length `len`.
+    /// * `Err(DominoError::GenerationError)` - If the template has no hole, no tile or holes not fitting in
+    ///   the puzzle without merging.
+    pub fn mask(&self, len: usize) -> Result<Vec<bool>, DominoError> {
+        let mask = match self {
+            HoleTemplate::Mask(mask) => {
+                if mask.len() != len {
+                    return Err(DominoError::InvalidLength);
+                }
+                mask.clone()
+            }
+            &HoleTemplate::EvenlySpaced { count, length } => {
+                let mut mask = vec![false; len];
+                for hole in 0..count {
+                    let start = hole * len / count.max(1);
+                    for offset in 0..length.min(len) {
+                        mask[(start + offset) % len] = true;
+                    }
+                }
+
+                // Holes too long for the spacing run into each other
+                let layout = template_layout(&mask);
+                if count == 0 || length == 0 || detect_holes(&layout).len() != count {
+                    return Err(DominoError::GenerationError(format!(
+                        "{} holes of length {} do not fit apart in a puzzle of length {}",
+                        count, length, len
+                    )));
+                }
+                mask
+            }
+        };
+
+        if !mask.contains(&true) {
+            return Err(DominoError::GenerationError(
+                "the template has no hole".to_string(),
+            ));
+        }
+        if mask.iter().all(|&empty| empty) {
+            return Err(DominoError::GenerationError(
+                "the template has no tile".to_string(),
+            ));
+        }
+
+        Ok(mask)
+    }
+}
+
+/// Returns a puzzle with the holes of the mask, the other positions holding placeholder tiles.
+///
+/// The holes alone determine the class of a puzzle, so the layout is classified like any puzzle
+/// following the mask.
+pub(super) fn template_layout(mask: &[bool]) -> Puzzle {
+    mask.iter()
+        .map(|&empty| (!empty).then_some(Tile(0, 0)))
+        .collect::<Vec<Option<Tile>>>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HoleTemplate;
+    use crate::{DominoError, Puzzle, Tile};
+
+    #[test]
+    fn test_template_evenly_spaced() {
+        let mask = HoleTemplate::EvenlySpaced { count: 3, length: 2 }.mask(15).unwrap();
+        let empty: Vec<usize> = (0..15).filter(|&position| mask[position]).collect();
+        assert_eq!(empty, vec![0, 1, 5, 6, 10, 11]);
+    }
+
+    #[test]
+    fn test_template_holes_merging() {
+        assert!(matches!(
+            HoleTemplate::EvenlySpaced { count: 3, length: 5 }.mask(15),
+            Err(DominoError::GenerationError(_))
+        ));
+        assert!(matches!(
+            HoleTemplate::EvenlySpaced { count: 0, length: 2 }.mask(15),
+            Err(DominoError::GenerationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_template_mask() {
+        let puzzle: Puzzle = vec![Some(Tile(0, 0)), None, Some(Tile(0, 1)), None].into();
+        let template = HoleTemplate::from_puzzle(&puzzle);
+        assert_eq!(template.mask(4), Ok(vec![false, true, false, true]));
+        assert_eq!(template.mask(5), Err(DominoError::InvalidLength));
+        assert!(HoleTemplate::Mask(vec![false; 4]).mask(4).is_err());
+    }
+}
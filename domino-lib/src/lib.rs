@@ -4,10 +4,37 @@ mod solve;
 mod utils;
 mod validate;
 
-pub use classify::{classify_puzzle, ComplexityClass, NUMBER_OF_CLASSES};
-pub use generate::generate_puzzle;
-pub use solve::solve_puzzle;
+pub use classify::{
+    classify_puzzle, classify_puzzle_by_deduction, classify_puzzle_by_deduction_with,
+    classify_puzzle_with, classify_puzzle_with_tileset, classify_puzzle_with_topology, BreakpointCurve, ClassificationScheme,
+    ComplexityClass, CurveEq, DeductionRating, FibonacciCurve, GeometricCurve, LinearCurve, ThresholdCurve,
+    NUMBER_OF_CLASSES,
+};
+pub use generate::{
+    generate_batch, generate_batch_with, generate_from_solution, generate_minimal,
+    generate_minimal_with, generate_puzzle, generate_puzzle_from_seed, generate_puzzle_with,
+    generate_puzzle_with_budget, generate_puzzle_with_rng, GeneratedPuzzle, GenerationBudget,
+    HoleTemplate, MinimalPuzzle, Minimality, PuzzleGenerator,
+};
+pub use solve::{
+    count_solutions, count_solutions_with, count_solutions_with_tileset,
+    count_solutions_with_topology, hint, solve_all, solve_all_with_tileset,
+    solve_all_with_topology, solve_puzzle, solve_puzzle_with, solve_puzzle_with_tileset,
+    solve_model, solve_puzzle_with_topology, Hint, HintReason, SolutionEquivalence, SolveMethod,
+};
 pub use utils::{
-    get_missing_tiles, Arc, DominoError, Graph, Node, Puzzle, Solution, Tile, Tournament, get_n
+    default_backend, find_eulerian_cycle, find_eulerian_cycle_with_rng, find_eulerian_path,
+    find_eulerian_path_with_rng, get_missing_tiles, get_missing_tiles_with, Arc, CanonicalPuzzle, ConstraintSense,
+    DominoError, Graph, LinearConstraint, LinearExpression, Model, ModelBuilder, ModelParseError,
+    Node, ObjectiveType, Puzzle, PuzzleEquivalence, ResultTranslator, Solution, SolverBackend, Tile, TileSet, Topology,
+    Tournament, VariableId, VariableKind, get_n
+};
+#[cfg(feature = "highs")]
+pub use utils::HighsBackend;
+#[cfg(feature = "microlp")]
+pub use utils::MicroLpBackend;
+pub use validate::{
+    check_uniqueness, validate_puzzle, validate_puzzle_report, validate_puzzle_report_with_tileset,
+    validate_model, validate_puzzle_report_with_topology, validate_puzzle_with_tileset,
+    validate_puzzle_with_topology, ValidationReport,
 };
-pub use validate::validate_puzzle;
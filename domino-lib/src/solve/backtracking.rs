@@ -0,0 +1,468 @@
+//! This module implements a native solver that completes a puzzle without building an LP model.
+//!
+//! The search fills the empty positions of the puzzle with the tiles of its `TileSet` not yet placed,
+//! always expanding the empty position with the fewest tiles compatible with its neighbours and
+//! backtracking as soon as a position is left without candidates.
+
+use crate::{
+    utils::{TileSet, Topology},
+    DominoError, Puzzle, Solution, Tile,
+};
+
+/// A choice point of the search: the position being filled and the candidates still to be tried.
+#[derive(Debug)]
+struct Frame {
+    position: usize,
+    candidates: Vec<(usize, Tile)>,
+}
+
+/// Depth-first search over the completions of a puzzle.
+///
+/// The tiles missing from the puzzle are kept unoriented in `tiles`, while `used` tracks which of them
+/// are currently placed. The copies of a same tile are used in order, so that swapping two copies
+/// never yields the same completion twice. Candidates are always produced already oriented, so that the right value of a
+/// tile matches the left value of the following one.
+///
+/// The search is resumable: as an `Iterator` it yields every distinct completion of the puzzle,
+/// each one exactly once.
+#[derive(Debug)]
+pub struct Backtracker {
+    puzzle: Puzzle,
+    topology: Topology,
+    tiles: Vec<Tile>,
+    used: Vec<bool>,
+    stack: Vec<Frame>,
+    started: bool,
+}
+
+impl Backtracker {
+    /// Creates a new search over the completions of the given puzzle.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle` - A reference to the `Puzzle` to complete.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Backtracker)` - If the puzzle has a valid length.
+    /// * `Err(DominoError::InvalidLength)` - If the puzzle length does not match any tileset.
+    pub fn new(puzzle: &Puzzle) -> Result<Self, DominoError> {
+        Backtracker::with_topology(puzzle, Topology::Closed)
+    }
+
+    /// Creates a new search over the completions of the given puzzle, linking its positions as in `topology`.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle` - A reference to the `Puzzle` to complete.
+    /// * `topology` - The `Topology` of the puzzle.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Backtracker)` - If the puzzle has a valid length.
+    /// * `Err(DominoError::InvalidLength)` - If the puzzle length does not match any tileset of the topology.
+    pub fn with_topology(puzzle: &Puzzle, topology: Topology) -> Result<Self, DominoError> {
+        let tileset = TileSet::for_puzzle(puzzle, topology)?;
+        Ok(Backtracker::from_missing_tiles(puzzle, tileset.missing_tiles(puzzle), topology))
+    }
+
+    /// Creates a new search over the completions of a puzzle made of the tiles of `tileset`.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle` - A reference to the `Puzzle` to complete.
+    /// * `tileset` - The `TileSet` the puzzle is made of.
+    /// * `topology` - The `Topology` of the puzzle.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Backtracker)` - If the puzzle has a position for each tile of the set.
+    /// * `Err(DominoError::InvalidLength)` - If the puzzle length differs from the size of the set.
+    pub fn with_tileset(
+        puzzle: &Puzzle,
+        tileset: &TileSet,
+        topology: Topology,
+    ) -> Result<Self, DominoError> {
+        if puzzle.len() != tileset.len() {
+            return Err(DominoError::InvalidLength);
+        }
+        Ok(Backtracker::from_missing_tiles(puzzle, tileset.missing_tiles(puzzle), topology))
+    }
+
+    /// Creates the search filling the puzzle with the given tiles, sorted in their `[low,high]` orientation.
+    fn from_missing_tiles(puzzle: &Puzzle, tiles: Vec<Tile>, topology: Topology) -> Self {
+        let used = vec![false; tiles.len()];
+        Backtracker {
+            puzzle: puzzle.clone(),
+            topology,
+            tiles,
+            used,
+            stack: Vec::new(),
+            started: false,
+        }
+    }
+
+    /// Searches for the first completion of the puzzle.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Solution)` - If a valid completion is found.
+    /// * `Err(DominoError::UnsolvablePuzzle)` - If the puzzle has no completion.
+    pub fn solve(mut self) -> Result<Solution, DominoError> {
+        self.next().ok_or(DominoError::UnsolvablePuzzle)
+    }
+
+    /// Checks that the tiles already placed can be part of a completion.
+    ///
+    /// The givens must all be tiles of the set, each placed at most as many times as it has copies (which is
+    /// the case exactly when the number of missing tiles equals the number of empty positions), and adjacent
+    /// givens must match.
+    pub(crate) fn is_consistent(&self) -> bool {
+        let empty_positions = self.puzzle.0.iter().filter(|tile| tile.is_none()).count();
+        if empty_positions != self.tiles.len() {
+            return false;
+        }
+
+        let len = self.puzzle.len();
+        (0..len).all(|position| {
+            let next = self.topology.next(position, len).and_then(|next| self.puzzle[next]);
+            match (self.puzzle[position], next) {
+                (Some(current), Some(next)) => current.1 == next.0,
+                _ => true,
+            }
+        })
+    }
+
+    /// Picks the empty position with the fewest candidates, returning `None` when the puzzle is full.
+    ///
+    /// A position without candidates is returned immediately, so that the caller backtracks right away.
+    fn select_position(&self) -> Option<Frame> {
+        let mut best: Option<Frame> = None;
+        for position in 0..self.puzzle.len() {
+            if self.puzzle[position].is_some() {
+                continue;
+            }
+            let candidates = self.candidates(position);
+            if candidates.is_empty() {
+                return Some(Frame {
+                    position,
+                    candidates,
+                });
+            }
+            if best
+                .as_ref()
+                .is_none_or(|frame| candidates.len() < frame.candidates.len())
+            {
+                best = Some(Frame {
+                    position,
+                    candidates,
+                });
+            }
+        }
+        best
+    }
+
+    /// Lists the unused tiles, already oriented, that fit at the given position.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The empty position of the puzzle to fill.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `(tile_index, oriented_tile)` pairs compatible with both neighbours.
+    pub(crate) fn candidates(&self, position: usize) -> Vec<(usize, Tile)> {
+        let len = self.puzzle.len();
+        let previous = self
+            .topology
+            .previous(position, len)
+            .and_then(|previous| self.puzzle[previous]);
+        let next = self.topology.next(position, len).and_then(|next| self.puzzle[next]);
+        let fits = |tile: Tile| {
+            previous.is_none_or(|previous| previous.1 == tile.0)
+                && next.is_none_or(|next| tile.1 == next.0)
+        };
+
+        let mut candidates = Vec::new();
+        for (index, tile) in self.tiles.iter().enumerate() {
+            // A copy of a tile is only tried once the previous copies are placed
+            let previous_copy_unused =
+                index > 0 && self.tiles[index - 1] == *tile && !self.used[index - 1];
+            if self.used[index] || previous_copy_unused {
+                continue;
+            }
+            if fits(*tile) {
+                candidates.push((index, *tile));
+            }
+            if tile.0 != tile.1 && fits(tile.flip()) {
+                candidates.push((index, tile.flip()));
+            }
+        }
+        // Candidates are popped from the back, reversing keeps the exploration in tileset order
+        candidates.reverse();
+        candidates
+    }
+
+    /// Returns the index of the last placed copy of the tile, the first one to be removed.
+    fn tile_index(&self, tile: Tile) -> usize {
+        (0..self.tiles.len())
+            .rev()
+            .find(|&index| self.used[index] && self.tiles[index] == tile)
+            .expect("Placed tiles always come from the missing tiles")
+    }
+
+    fn solution(&self) -> Solution {
+        self.puzzle.0.iter().map(|tile| tile.unwrap()).collect()
+    }
+}
+
+impl Iterator for Backtracker {
+    type Item = Solution;
+
+    /// Resumes the search from the last completion found and returns the next one.
+    fn next(&mut self) -> Option<Solution> {
+        if !self.started {
+            self.started = true;
+            if !self.is_consistent() {
+                return None;
+            }
+            match self.select_position() {
+                Some(frame) => self.stack.push(frame),
+                None => return Some(self.solution()),
+            }
+        }
+
+        while let Some(frame) = self.stack.last() {
+            let position = frame.position;
+            // The previous candidate placed at this position is removed before trying the next one
+            if let Some(tile) = self.puzzle[position].take() {
+                let index = self.tile_index(tile);
+                self.used[index] = false;
+            }
+
+            let frame = self.stack.last_mut().unwrap();
+            match frame.candidates.pop() {
+                Some((index, tile)) => {
+                    self.used[index] = true;
+                    self.puzzle[position] = Some(tile);
+                    match self.select_position() {
+                        Some(next_frame) => self.stack.push(next_frame),
+                        None => return Some(self.solution()),
+                    }
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Backtracker;
+    use crate::{DominoError, Puzzle, Tile, TileSet, Topology};
+
+    fn assert_is_completion(puzzle: &Puzzle, solution: &Vec<Tile>) {
+        assert_eq!(solution.len(), puzzle.len());
+        for (position, tile) in solution.iter().enumerate() {
+            if let Some(given) = puzzle[position] {
+                assert_eq!(given.0, tile.0);
+                assert_eq!(given.1, tile.1);
+            }
+            assert_eq!(tile.1, solution[(position + 1) % solution.len()].0);
+        }
+    }
+
+    #[test]
+    fn test_backtracking_single_hole() {
+        let puzzle: Puzzle = vec![
+            Some((0, 0).into()),
+            Some((0, 1).into()),
+            Some((1, 1).into()),
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            None,
+            None,
+            None,
+        ]
+        .into();
+        let solution = Backtracker::new(&puzzle).unwrap().solve().unwrap();
+        assert_is_completion(&puzzle, &solution);
+        assert_eq!(solution[5], Tile(2, 3));
+        assert_eq!(solution[6], Tile(3, 3));
+        assert_eq!(solution[7], Tile(3, 0));
+    }
+
+    #[test]
+    fn test_backtracking_multiple_holes() {
+        let puzzle: Puzzle = vec![
+            None,
+            Some((0, 1).into()),
+            None,
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            None,
+            None,
+            None,
+        ]
+        .into();
+        let solution = Backtracker::new(&puzzle).unwrap().solve().unwrap();
+        assert_is_completion(&puzzle, &solution);
+    }
+
+    #[test]
+    fn test_backtracking_empty_puzzle() {
+        for len in [8, 15, 18] {
+            let puzzle: Puzzle = vec![None; len].into();
+            let solution = Backtracker::new(&puzzle).unwrap().solve().unwrap();
+            assert_is_completion(&puzzle, &solution);
+        }
+    }
+
+    #[test]
+    fn test_backtracking_duplicated_tile() {
+        let puzzle: Puzzle = vec![
+            Some((0, 1).into()),
+            Some((1, 0).into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ]
+        .into();
+        let result = Backtracker::new(&puzzle).unwrap().solve();
+        assert_eq!(result, Err(DominoError::UnsolvablePuzzle));
+    }
+
+    #[test]
+    fn test_backtracking_mismatching_neighbours() {
+        let puzzle: Puzzle = vec![
+            Some((0, 1).into()),
+            Some((2, 2).into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ]
+        .into();
+        let result = Backtracker::new(&puzzle).unwrap().solve();
+        assert_eq!(result, Err(DominoError::UnsolvablePuzzle));
+    }
+
+    #[test]
+    fn test_backtracking_enumerates_distinct_completions() {
+        let puzzle: Puzzle = vec![
+            Some((0, 0).into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ]
+        .into();
+        let solutions: Vec<Vec<Tile>> = Backtracker::new(&puzzle).unwrap().collect();
+        assert!(solutions.len() > 1);
+        for (i, solution) in solutions.iter().enumerate() {
+            assert_is_completion(&puzzle, solution);
+            for other in solutions.iter().skip(i + 1) {
+                let same = solution
+                    .iter()
+                    .zip(other.iter())
+                    .all(|(a, b)| a.0 == b.0 && a.1 == b.1);
+                assert!(!same);
+            }
+        }
+    }
+
+    #[test]
+    fn test_backtracking_unique_completion() {
+        let puzzle: Puzzle = vec![
+            None,
+            Some((0, 1).into()),
+            None,
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            None,
+            None,
+            None,
+        ]
+        .into();
+        assert_eq!(Backtracker::new(&puzzle).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_backtracking_full_puzzle() {
+        let puzzle: Puzzle = vec![
+            Some((0, 0).into()),
+            Some((0, 1).into()),
+            Some((1, 1).into()),
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            Some((2, 3).into()),
+            Some((3, 3).into()),
+            Some((3, 0).into()),
+        ]
+        .into();
+        assert_eq!(Backtracker::new(&puzzle).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_backtracking_open_chain() {
+        // The open tileset of size 3 keeps [0,2], so the chain runs from 0 to 2 without closing
+        let puzzle: Puzzle = vec![
+            Some((0, 0).into()),
+            Some((0, 1).into()),
+            Some((1, 1).into()),
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            Some((2, 3).into()),
+            None,
+            None,
+            Some((0, 2).into()),
+        ]
+        .into();
+        assert_eq!(Backtracker::new(&puzzle).unwrap().count(), 0);
+        let solutions: Vec<Vec<Tile>> = Backtracker::with_topology(&puzzle, Topology::Open)
+            .unwrap()
+            .collect();
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0][6], Tile(3, 3));
+        assert_eq!(solutions[0][7], Tile(3, 0));
+    }
+
+    #[test]
+    fn test_backtracking_invalid_length() {
+        let puzzle: Puzzle = vec![None; 7].into();
+        assert_eq!(
+            Backtracker::new(&puzzle).err(),
+            Some(DominoError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_backtracking_duplicate_tiles() {
+        // Swapping the two copies of [0,1] must not yield a second completion
+        let tileset = TileSet::new(vec![Tile(0, 0), Tile(0, 1), Tile(0, 1), Tile(1, 1)]).unwrap();
+        let puzzle: Puzzle = vec![Some(Tile(0, 0)), None, None, None].into();
+        let solutions: Vec<Vec<Tile>> = Backtracker::with_tileset(&puzzle, &tileset, Topology::Closed)
+            .unwrap()
+            .collect();
+        assert_eq!(solutions, vec![vec![Tile(0, 0), Tile(0, 1), Tile(1, 1), Tile(1, 0)]]);
+        assert_is_completion(&puzzle, &solutions[0]);
+
+        let puzzle: Puzzle = vec![None; 5].into();
+        assert_eq!(
+            Backtracker::with_tileset(&puzzle, &tileset, Topology::Closed).err(),
+            Some(DominoError::InvalidLength)
+        );
+    }
+}
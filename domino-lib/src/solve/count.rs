@@ -0,0 +1,203 @@
+//! This module counts the completions of a puzzle, optionally identifying symmetric completions.
+//!
+//! A completion of a closed puzzle is a cycle of tiles, so it can be rotated (changing the starting position)
+//! or reflected (reading it backwards, flipping every tile) without changing the underlying cycle. The
+//! completion of an open puzzle is a line with fixed ends, which can only be reflected.
+
+use std::collections::HashSet;
+
+use super::backtracking::Backtracker;
+use crate::{
+    utils::{canonical_key, TileSet, Topology},
+    DominoError, Puzzle, PuzzleEquivalence,
+};
+
+/// Selects which completions of a puzzle are considered the same when counting them.
+///
+/// Completions are compared as the puzzles they fill, see `PuzzleEquivalence`.
+pub type SolutionEquivalence = PuzzleEquivalence;
+
+/// Counts the completions of a closed puzzle, up to the given equivalence.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` whose completions are counted.
+/// * `equivalence` - The `SolutionEquivalence` used to identify completions.
+///
+/// # Returns
+///
+/// * `Ok(u64)` - The number of (classes of) completions, `0` if the puzzle is unsolvable.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length is invalid.
+pub fn count(puzzle: &Puzzle, equivalence: SolutionEquivalence) -> Result<u64, DominoError> {
+    count_with_topology(puzzle, equivalence, Topology::Closed)
+}
+
+/// Counts the completions of a puzzle of the given `Topology`, up to the given equivalence.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` whose completions are counted.
+/// * `equivalence` - The `SolutionEquivalence` used to identify completions.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// * `Ok(u64)` - The number of (classes of) completions, `0` if the puzzle is unsolvable.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length does not match any tileset of the topology.
+pub fn count_with_topology(
+    puzzle: &Puzzle,
+    equivalence: SolutionEquivalence,
+    topology: Topology,
+) -> Result<u64, DominoError> {
+    let solutions = Backtracker::with_topology(puzzle, topology)?;
+    Ok(count_completions(solutions, equivalence, topology))
+}
+
+/// Counts the completions of a puzzle made of the tiles of an explicit `TileSet`, up to the given equivalence.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` whose completions are counted.
+/// * `equivalence` - The `SolutionEquivalence` used to identify completions.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// * `Ok(u64)` - The number of (classes of) completions, `0` if the puzzle is unsolvable.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length differs from the size of the set.
+pub fn count_with_tileset(
+    puzzle: &Puzzle,
+    equivalence: SolutionEquivalence,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<u64, DominoError> {
+    let solutions = Backtracker::with_tileset(puzzle, tileset, topology)?;
+    Ok(count_completions(solutions, equivalence, topology))
+}
+
+/// Counts the completions enumerated by the search, identifying the ones sharing a canonical form.
+fn count_completions(
+    solutions: Backtracker,
+    equivalence: SolutionEquivalence,
+    topology: Topology,
+) -> u64 {
+    if equivalence == SolutionEquivalence::Exact {
+        return solutions.count() as u64;
+    }
+
+    let classes: HashSet<Vec<Option<(i32, i32)>>> = solutions
+        .map(|solution| {
+            let completion: Puzzle = solution.into_iter().map(Some).collect::<Vec<_>>().into();
+            canonical_key(&completion, equivalence, topology)
+        })
+        .collect();
+    classes.len() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count, count_with_tileset, count_with_topology, SolutionEquivalence};
+    use crate::{DominoError, Puzzle, Tile, TileSet, Topology};
+
+    #[test]
+    fn test_count_unique_puzzle() {
+        let puzzle: Puzzle = vec![
+            None,
+            Some((0, 1).into()),
+            None,
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            None,
+            None,
+            None,
+        ]
+        .into();
+        assert_eq!(count(&puzzle, SolutionEquivalence::Exact), Ok(1));
+        assert_eq!(count(&puzzle, SolutionEquivalence::Rotation), Ok(1));
+        assert_eq!(
+            count(&puzzle, SolutionEquivalence::RotationAndReflection),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_count_unsolvable_puzzle() {
+        let puzzle: Puzzle = vec![
+            Some((0, 1).into()),
+            Some((2, 2).into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ]
+        .into();
+        assert_eq!(count(&puzzle, SolutionEquivalence::Exact), Ok(0));
+    }
+
+    #[test]
+    fn test_count_empty_puzzle_equivalences() {
+        // Without givens every rotation of a completion is another completion
+        let puzzle: Puzzle = vec![None; 6].into();
+        let exact = count(&puzzle, SolutionEquivalence::Exact).unwrap();
+        let rotation = count(&puzzle, SolutionEquivalence::Rotation).unwrap();
+        let reflection = count(&puzzle, SolutionEquivalence::RotationAndReflection).unwrap();
+        assert_eq!(exact, rotation * puzzle.len() as u64);
+        assert!(reflection < rotation);
+        assert!(reflection * 2 >= rotation);
+    }
+
+    #[test]
+    fn test_count_invalid_length() {
+        let puzzle: Puzzle = vec![None; 7].into();
+        assert_eq!(
+            count(&puzzle, SolutionEquivalence::Exact),
+            Err(DominoError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_count_open_puzzle() {
+        // The rotations of an open completion move its ends, so they are not the same completion
+        let puzzle: Puzzle = vec![None; 6].into();
+        let exact = count_with_topology(&puzzle, SolutionEquivalence::Exact, Topology::Open).unwrap();
+        let rotation =
+            count_with_topology(&puzzle, SolutionEquivalence::Rotation, Topology::Open).unwrap();
+        let reflection = count_with_topology(
+            &puzzle,
+            SolutionEquivalence::RotationAndReflection,
+            Topology::Open,
+        )
+        .unwrap();
+        assert!(exact > 0);
+        assert_eq!(rotation, exact);
+        assert!(reflection < rotation);
+        assert!(reflection * 2 >= rotation);
+    }
+
+    #[test]
+    fn test_count_tileset() {
+        let tileset = TileSet::new(vec![Tile(0, 0), Tile(0, 1), Tile(0, 1), Tile(1, 1)]).unwrap();
+        let puzzle: Puzzle = vec![Some(Tile(0, 0)), None, None, None].into();
+        assert_eq!(
+            count_with_tileset(&puzzle, SolutionEquivalence::Exact, &tileset, Topology::Closed),
+            Ok(1)
+        );
+
+        let puzzle: Puzzle = vec![None; 4].into();
+        let exact =
+            count_with_tileset(&puzzle, SolutionEquivalence::Exact, &tileset, Topology::Closed)
+                .unwrap();
+        let reflection = count_with_tileset(
+            &puzzle,
+            SolutionEquivalence::RotationAndReflection,
+            &tileset,
+            Topology::Closed,
+        )
+        .unwrap();
+        assert!(exact > 1);
+        assert_eq!(reflection, 1);
+    }
+}
@@ -0,0 +1,272 @@
+//! This module provides step-by-step hints, deducing placements forced by the tiles left and their neighbours.
+//!
+//! Hints are derived only from local reasoning over the missing tiles, without solving the whole puzzle,
+//! so that each of them can be explained to the player:
+//! - an empty position where a single tile fits given its neighbours;
+//! - a missing tile that fits in a single empty position.
+
+use std::collections::HashMap;
+
+use super::backtracking::Backtracker;
+use crate::{DominoError, Puzzle, Tile};
+
+/// The deduction that forces the placement suggested by a `Hint`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintReason {
+    /// The tile is the only one left that fits between the neighbours of the position.
+    OnlyTileForPosition {
+        /// The tile placed before the position, if any.
+        previous: Option<Tile>,
+        /// The tile placed after the position, if any.
+        next: Option<Tile>,
+    },
+    /// The position is the only empty one where the tile fits.
+    OnlyPositionForTile,
+}
+
+/// A placement forced by the current state of the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hint {
+    /// The empty position to fill.
+    pub position: usize,
+    /// The tile to place, already oriented so that its values match the neighbours.
+    pub tile: Tile,
+    /// Whether the tile is placed reversed with respect to its canonical `[low,high]` orientation.
+    pub flipped: bool,
+    /// The deduction forcing the placement.
+    pub reason: HintReason,
+}
+
+impl std::fmt::Display for HintReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HintReason::OnlyTileForPosition {
+                previous: Some(previous),
+                next: Some(next),
+            } => write!(
+                f,
+                "only tile left fitting between {} and {}",
+                previous, next
+            ),
+            HintReason::OnlyTileForPosition {
+                previous: Some(previous),
+                next: None,
+            } => write!(
+                f,
+                "only tile left containing {} adjacent to {}",
+                previous.1, previous
+            ),
+            HintReason::OnlyTileForPosition {
+                previous: None,
+                next: Some(next),
+            } => write!(
+                f,
+                "only tile left containing {} adjacent to {}",
+                next.0, next
+            ),
+            HintReason::OnlyTileForPosition {
+                previous: None,
+                next: None,
+            } => write!(f, "only tile left"),
+            HintReason::OnlyPositionForTile => {
+                write!(f, "the tile fits in no other empty position")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Hint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "place {} at position {}: {}",
+            self.tile, self.position, self.reason
+        )
+    }
+}
+
+/// Finds the next placement forced by the missing tiles and the neighbour constraints.
+///
+/// Positions are inspected first, in order, looking for one where a single tile fits; then the missing
+/// tiles are inspected, looking for one that fits in a single position.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` representing the current state of the game.
+///
+/// # Returns
+///
+/// * `Ok(Some(Hint))` - The next forced placement and its reason.
+/// * `Ok(None)` - If the puzzle is complete, inconsistent, or no placement can be deduced locally.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length is invalid.
+pub fn hint(puzzle: &Puzzle) -> Result<Option<Hint>, DominoError> {
+    let backtracker = Backtracker::new(puzzle)?;
+    if !backtracker.is_consistent() {
+        return Ok(None);
+    }
+
+    let len = puzzle.len();
+    let empty_positions: Vec<usize> = (0..len)
+        .filter(|&position| puzzle[position].is_none())
+        .collect();
+    let candidates: Vec<Vec<(usize, Tile)>> = empty_positions
+        .iter()
+        .map(|&position| backtracker.candidates(position))
+        .collect();
+
+    // A position left without candidates means the current state cannot be completed
+    if candidates.iter().any(|candidates| candidates.is_empty()) {
+        return Ok(None);
+    }
+
+    for (&position, candidates) in empty_positions.iter().zip(candidates.iter()) {
+        if let [(_, tile)] = candidates.as_slice() {
+            return Ok(Some(Hint {
+                position,
+                tile: *tile,
+                flipped: tile.0 > tile.1,
+                reason: HintReason::OnlyTileForPosition {
+                    previous: puzzle[(position + len - 1) % len],
+                    next: puzzle[(position + 1) % len],
+                },
+            }));
+        }
+    }
+
+    let mut placements: HashMap<usize, Vec<(usize, Tile)>> = HashMap::new();
+    for (&position, candidates) in empty_positions.iter().zip(candidates.iter()) {
+        for (index, tile) in candidates {
+            placements
+                .entry(*index)
+                .or_default()
+                .push((position, *tile));
+        }
+    }
+    let mut forced: Vec<(usize, (usize, Tile))> = placements
+        .into_iter()
+        .filter(|(_, placements)| placements.len() == 1)
+        .map(|(index, placements)| (index, placements[0]))
+        .collect();
+    forced.sort_by_key(|(index, _)| *index);
+
+    Ok(forced.first().map(|(_, (position, tile))| Hint {
+        position: *position,
+        tile: *tile,
+        flipped: tile.0 > tile.1,
+        reason: HintReason::OnlyPositionForTile,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hint, HintReason};
+    use crate::{DominoError, Puzzle, Tile};
+
+    #[test]
+    fn test_hint_only_tile_for_position() {
+        let puzzle: Puzzle = vec![
+            Some((0, 0).into()),
+            Some((0, 1).into()),
+            Some((1, 1).into()),
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            None,
+            Some((3, 3).into()),
+            Some((3, 0).into()),
+        ]
+        .into();
+        let hint = hint(&puzzle).unwrap().unwrap();
+        assert_eq!(hint.position, 5);
+        assert_eq!((hint.tile.0, hint.tile.1), (2, 3));
+        assert!(!hint.flipped);
+        assert_eq!(
+            hint.reason,
+            HintReason::OnlyTileForPosition {
+                previous: Some(Tile(2, 2)),
+                next: Some(Tile(3, 3)),
+            }
+        );
+        assert_eq!(
+            hint.to_string(),
+            "place [2,3] at position 5: only tile left fitting between [2,2] and [3,3]"
+        );
+    }
+
+    #[test]
+    fn test_hint_is_part_of_the_solution() {
+        let puzzle: Puzzle = vec![
+            None,
+            Some((0, 1).into()),
+            None,
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            None,
+            None,
+            None,
+        ]
+        .into();
+        let solution = [
+            (0, 0),
+            (0, 1),
+            (1, 1),
+            (1, 2),
+            (2, 2),
+            (2, 3),
+            (3, 3),
+            (3, 0),
+        ];
+        let mut puzzle = puzzle;
+        while let Some(hint) = hint(&puzzle).unwrap() {
+            assert_eq!((hint.tile.0, hint.tile.1), solution[hint.position]);
+            puzzle[hint.position] = Some(hint.tile);
+        }
+    }
+
+    #[test]
+    fn test_hint_only_position_for_tile() {
+        // Every position has several candidates, but [0,0] can only follow [3,0]
+        let puzzle: Puzzle = vec![
+            None,
+            None,
+            Some((1, 1).into()),
+            None,
+            None,
+            Some((2, 3).into()),
+            Some((3, 3).into()),
+            Some((3, 0).into()),
+        ]
+        .into();
+        let hint = hint(&puzzle).unwrap().unwrap();
+        assert_eq!(hint.reason, HintReason::OnlyPositionForTile);
+        assert_eq!(hint.position, 0);
+        assert_eq!((hint.tile.0, hint.tile.1), (0, 0));
+    }
+
+    #[test]
+    fn test_hint_no_deduction() {
+        let puzzle: Puzzle = vec![None; 8].into();
+        assert_eq!(hint(&puzzle), Ok(None));
+    }
+
+    #[test]
+    fn test_hint_inconsistent_puzzle() {
+        let puzzle: Puzzle = vec![
+            Some((0, 1).into()),
+            Some((2, 2).into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ]
+        .into();
+        assert_eq!(hint(&puzzle), Ok(None));
+    }
+
+    #[test]
+    fn test_hint_invalid_length() {
+        let puzzle: Puzzle = vec![None; 7].into();
+        assert_eq!(hint(&puzzle), Err(DominoError::InvalidLength));
+    }
+}
@@ -1,62 +1,324 @@
+mod backtracking;
+mod count;
+mod hint;
 mod model;
 
-use model::{compute_model, variables::create_tileset};
+use backtracking::Backtracker;
+use good_lp::ResolutionError;
+pub use count::SolutionEquivalence;
+pub use hint::{hint, Hint, HintReason};
+use model::{compute_model, compute_model_with_variables, variables::create_tileset};
 
 use crate::{
-    utils::{get_n, Model},
+    utils::{Model, ModelBuilder, TileSet, Topology},
     DominoError, Puzzle, Solution, Tile,
 };
 
-/// Attempts to solve a given puzzle using a recursive backtracking approach.
+/// Selects the algorithm used to complete a puzzle.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SolveMethod {
+    /// Builds the linear model of the puzzle and runs it through `Model::execute_builder`.
+    #[default]
+    Model,
+    /// Fills the empty positions with a native constraint-propagation and backtracking search.
+    Backtracking,
+}
+
+/// Attempts to solve a given puzzle with the selected `SolveMethod`.
 ///
-/// This function clones the puzzle, determines the missing tiles, and then attempts to solve
-/// the puzzle by filling in the missing tiles while ensuring valid adjacency constraints.
+/// Both methods return the same `Solution` type and error variants, so they can be used interchangeably.
 ///
 /// # Arguments
 ///
 /// * `puzzle` - A reference to the `Puzzle` structure representing the current puzzle state.
+/// * `method` - The `SolveMethod` used to compute the solution.
 ///
 /// # Returns
 ///
 /// * `Ok(Solution)` - If a valid solution is found.
 /// * `Err(DominoError::UnsolvablePuzzle)` - If no solution exists.
-/// * `Err(DominoError::InvalidPuzzle)` - If the puzzle input is invalid.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length is invalid.
+pub fn solve_puzzle_with(puzzle: &Puzzle, method: SolveMethod) -> Result<Solution, DominoError> {
+    solve_puzzle_with_topology(puzzle, method, Topology::Closed)
+}
+
+/// Attempts to solve a puzzle of the given `Topology` with the selected `SolveMethod`.
+///
+/// The last position of an open puzzle is not linked to the first one, so its completions are open chains.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` structure representing the current puzzle state.
+/// * `method` - The `SolveMethod` used to compute the solution.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// * `Ok(Solution)` - If a valid solution is found.
+/// * `Err(DominoError)` - If an error occurs, as for `solve_puzzle_with`.
+pub fn solve_puzzle_with_topology(
+    puzzle: &Puzzle,
+    method: SolveMethod,
+    topology: Topology,
+) -> Result<Solution, DominoError> {
+    match method {
+        SolveMethod::Model => solve_by_model(puzzle, &TileSet::for_puzzle(puzzle, topology)?, topology),
+        SolveMethod::Backtracking => Backtracker::with_topology(puzzle, topology)?.solve(),
+    }
+}
+
+/// Attempts to solve a puzzle made of the tiles of an explicit `TileSet` with the selected `SolveMethod`.
+///
+/// The puzzle has a position for each tile of the set, copies included, and its completions place every
+/// tile of the set.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` structure representing the current puzzle state.
+/// * `method` - The `SolveMethod` used to compute the solution.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// * `Ok(Solution)` - If a valid solution is found.
+/// * `Err(DominoError::UnsolvablePuzzle)` - If no solution exists, for instance if a given is not in the set.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length differs from the size of the set.
+/// * `Err(DominoError::ModelError)` - If the model execution fails.
+pub fn solve_puzzle_with_tileset(
+    puzzle: &Puzzle,
+    method: SolveMethod,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<Solution, DominoError> {
+    match method {
+        SolveMethod::Model => solve_by_model(puzzle, tileset, topology),
+        SolveMethod::Backtracking => Backtracker::with_tileset(puzzle, tileset, topology)?.solve(),
+    }
+}
+
+/// Enumerates the distinct completions of a puzzle, up to a maximum number of solutions.
+///
+/// The completions are produced lazily by the native backtracking search, so asking for a small
+/// `limit` only explores the part of the search space needed to find them.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` structure representing the current puzzle state.
+/// * `limit` - The maximum number of solutions yielded by the iterator.
+///
+/// # Returns
+///
+/// * `Ok(impl Iterator<Item = Solution>)` - An iterator over at most `limit` distinct solutions,
+///   empty if the puzzle has no solution.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length is invalid.
+pub fn solve_all(
+    puzzle: &Puzzle,
+    limit: usize,
+) -> Result<impl Iterator<Item = Solution>, DominoError> {
+    solve_all_with_topology(puzzle, limit, Topology::Closed)
+}
+
+/// Enumerates the distinct completions of a puzzle of the given `Topology`, up to a maximum number of solutions.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` structure representing the current puzzle state.
+/// * `limit` - The maximum number of solutions yielded by the iterator.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// * `Ok(impl Iterator<Item = Solution>)` - An iterator over at most `limit` distinct solutions.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length does not match any tileset of the topology.
+pub fn solve_all_with_topology(
+    puzzle: &Puzzle,
+    limit: usize,
+    topology: Topology,
+) -> Result<impl Iterator<Item = Solution>, DominoError> {
+    Ok(Backtracker::with_topology(puzzle, topology)?.take(limit))
+}
+
+/// Enumerates the distinct completions of a puzzle made of the tiles of an explicit `TileSet`.
+///
+/// Completions only differing by which copy of a tile is placed where are the same completion.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` structure representing the current puzzle state.
+/// * `limit` - The maximum number of solutions yielded by the iterator.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// * `Ok(impl Iterator<Item = Solution>)` - An iterator over at most `limit` distinct solutions.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length differs from the size of the set.
+pub fn solve_all_with_tileset(
+    puzzle: &Puzzle,
+    limit: usize,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<impl Iterator<Item = Solution>, DominoError> {
+    Ok(Backtracker::with_tileset(puzzle, tileset, topology)?.take(limit))
+}
+
+/// Counts every distinct completion of a closed puzzle of the regular tileset.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` whose completions are counted.
+///
+/// # Returns
+///
+/// * `Ok(u64)` - The number of completions, `0` if the puzzle is unsolvable and `1` if it is unique.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length is invalid.
+pub fn count_solutions(puzzle: &Puzzle) -> Result<u64, DominoError> {
+    count::count(puzzle, SolutionEquivalence::Exact)
+}
+
+/// Counts the completions of a closed puzzle, identifying the ones equivalent under the given `SolutionEquivalence`.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` whose completions are counted.
+/// * `equivalence` - The `SolutionEquivalence` identifying completions, as the puzzles they fill.
+///
+/// # Returns
+///
+/// * `Ok(u64)` - The number of classes of equivalent completions.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length is invalid.
+pub fn count_solutions_with(
+    puzzle: &Puzzle,
+    equivalence: SolutionEquivalence,
+) -> Result<u64, DominoError> {
+    count::count(puzzle, equivalence)
+}
+
+/// Counts the completions of a puzzle of the given `Topology`, up to the given `SolutionEquivalence`.
+///
+/// The completions of an open puzzle are lines with fixed ends, so they are only identified with their
+/// reflection.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` whose completions are counted.
+/// * `equivalence` - The `SolutionEquivalence` identifying completions, as the puzzles they fill.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// * `Ok(u64)` - The number of classes of equivalent completions.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length does not match any tileset of the topology.
+pub fn count_solutions_with_topology(
+    puzzle: &Puzzle,
+    equivalence: SolutionEquivalence,
+    topology: Topology,
+) -> Result<u64, DominoError> {
+    count::count_with_topology(puzzle, equivalence, topology)
+}
+
+/// Counts the completions of a puzzle made of the tiles of an explicit `TileSet`.
+///
+/// Completions only differing by which copy of a tile is placed where are the same completion.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` whose completions are counted.
+/// * `equivalence` - The `SolutionEquivalence` identifying completions, as the puzzles they fill.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// * `Ok(u64)` - The number of classes of equivalent completions.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length differs from the size of the set.
+pub fn count_solutions_with_tileset(
+    puzzle: &Puzzle,
+    equivalence: SolutionEquivalence,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<u64, DominoError> {
+    count::count_with_tileset(puzzle, equivalence, tileset, topology)
+}
+
+/// Attempts to solve a given puzzle by computing and executing its LP model.
+///
+/// This function builds the model of the puzzle, executes it and then maps the variables set to one
+/// back to the tiles placed in each position of the puzzle.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` structure representing the current puzzle state.
+///
+/// # Returns
+///
+/// * `Ok(Solution)` - If a valid solution is found.
+/// * `Err(DominoError::UnsolvablePuzzle)` - If no solution exists.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length is invalid.
+/// * `Err(DominoError::ModelError)` - If the model execution fails.
 pub fn solve_puzzle(puzzle: &Puzzle) -> Result<Solution, DominoError> {
-    let model_string = compute_model(puzzle)?;
-    // println!("Model: {}", model_string);
+    solve_by_model(puzzle, &TileSet::for_puzzle(puzzle, Topology::Closed)?, Topology::Closed)
+}
+
+/// Builds the linear model executed by `SolveMethod::Model` to complete a puzzle.
+///
+/// The model can be exported with `ModelBuilder::to_lp` or `ModelBuilder::to_mps` to inspect it with
+/// other tools.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` structure representing the current puzzle state.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle.
+///
+/// # Returns
+///
+/// * `Ok(ModelBuilder)` - The model of the puzzle.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length differs from the size of the set.
+pub fn solve_model(
+    puzzle: &Puzzle,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<ModelBuilder, DominoError> {
+    compute_model(puzzle, tileset, topology)
+}
+
+/// Solves a puzzle of the given tileset and topology through its LP model, as `solve_puzzle` does.
+fn solve_by_model(
+    puzzle: &Puzzle,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<Solution, DominoError> {
+    // Givens outside of the set, or placed more often than they have copies, leave no completion
+    let empty_positions = puzzle.0.iter().filter(|tile| tile.is_none()).count();
+    if puzzle.len() == tileset.len() && tileset.missing_tiles(puzzle).len() != empty_positions {
+        return Err(DominoError::UnsolvablePuzzle);
+    }
+
+    let (model, variables) = compute_model_with_variables(puzzle, tileset, topology)?;
     // Execute the model to obtain a solver result.
-    let solver_result = Model::execute(model_string.clone());
+    let solver_result = Model::execute_builder(&model);
 
-    let n = get_n(puzzle)?;
-    let tileset: Vec<Tile> = create_tileset(n as usize)
+    let tileset: Vec<Tile> = create_tileset(tileset)
         .iter()
         .map(|tuple| Tile((*tuple).0 as i32, (*tuple).1 as i32).into())
         .collect();
-    let tileset_digits = (tileset.len() as f32).log10().floor() as usize + 1;
-    let sequence_digits = (puzzle.0.len() as f32).log10().floor() as usize + 1;
     if let Ok(translator) = solver_result {
+        // Each variable set to 1 places an oriented tile of the set at a position
         let mut solution = puzzle.clone();
-        let variables = translator._get_variables();
-        let labels: Vec<String> = variables
-            .iter()
-            .filter_map(|entry: (&String, &f64)| {
-                if entry.1 == &1.0 {
-                    Some(entry.0.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
-        // println!("Labels: {labels:?}");
-        labels.iter().for_each(|label| {
-            let tile_index: usize = label[1..1 + tileset_digits].parse().unwrap();
-            let position_index: usize = label
-                [1 + tileset_digits..1 + tileset_digits + sequence_digits]
-                .parse()
-                .unwrap();
-            solution.0[position_index] = Some(tileset[tile_index])
-        });
-        Ok(solution.0.iter().map(|option| option.unwrap()).collect())
+        for (variable, (tile_index, position_index)) in variables.placements() {
+            if translator.value(variable) == Some(1.0) {
+                solution.0[position_index] = Some(tileset[tile_index]);
+            }
+        }
+        solution
+            .0
+            .into_iter()
+            .collect::<Option<Solution>>()
+            .ok_or_else(|| DominoError::ModelError("The model left a position empty".to_string()))
+    } else if let Err(ResolutionError::Infeasible) = solver_result {
+        Err(DominoError::UnsolvablePuzzle)
     } else {
         Err(DominoError::ModelError(
             "Model failed execution".to_string(),
@@ -1,13 +1,12 @@
 //! This module provides utility functions and macros for sorting variable labels,
-//! formatting variable collections, and constructing constraint expressions.
-
-use crate::stringify_variables;
+//! formatting variable collections, and constructing constraints.
 
 use super::variables::Variable;
+use crate::utils::{ConstraintSense, LinearConstraint, LinearExpression, VariableId};
 
-/// Collects variable labels from a slice of `Variable` structs.
+/// Collects the ids of a slice of `Variable` structs.
 ///
-/// This function extracts the `label` field from each `Variable` in the provided slice.
+/// This function extracts the `id` field from each `Variable` in the provided slice.
 ///
 /// # Arguments
 ///
@@ -15,23 +14,24 @@ use super::variables::Variable;
 ///
 /// # Returns
 ///
-/// A `Vec<String>` containing the labels of all variables.
-pub fn collect_labels(variables: &[Variable]) -> Vec<String> {
-    variables.iter().map(|var| var.label.clone()).collect()
+/// A `Vec<VariableId>` containing the ids of all variables.
+pub fn collect_ids(variables: &[Variable]) -> Vec<VariableId> {
+    variables.iter().map(|var| var.id).collect()
 }
 
-/// Creates a constraint expression enforcing a sum of binary variables.
+/// Creates a constraint enforcing a sum of binary variables.
 ///
-/// This function generates a constraint in the form of `"var1 var2 ... varn = 1"`, ensuring that
-/// exactly one of the listed variables is active in the solution.
+/// This function generates a constraint in the form of `var1 + var2 + ... + varn = total`, ensuring that
+/// exactly `total` of the listed variables are active in the solution.
 ///
 /// # Arguments
 ///
-/// * `labels` - A vector of variable labels (`Vec<String>`) that should be included in the constraint.
+/// * `ids` - The variables that should be included in the constraint.
+/// * `total` - The number of variables to activate.
 ///
 /// # Returns
 ///
-/// A string representing the constraint expression.
-pub fn create_bound_string(labels: Vec<String>) -> String {
-    format!("{} = 1", stringify_variables!(labels, " "))
+/// The `LinearConstraint` enforcing the sum.
+pub fn create_bound(ids: Vec<VariableId>, total: usize) -> LinearConstraint {
+    LinearConstraint::new(LinearExpression::sum(ids), ConstraintSense::Equal, total as f64)
 }
@@ -4,7 +4,7 @@
 //! using mathematical optimization principles.
 
 use bounds::partial_tiles_bound;
-use crate::utils::{DominoError, Puzzle};
+use crate::utils::{DominoError, LinearConstraint, LinearExpression, ModelBuilder, Puzzle, TileSet, Topology};
 
 use super::model::bounds::{
     each_position_filled_bound, each_tile_used_once_bound, next_adjacent_bound,
@@ -26,23 +26,28 @@ pub mod variables;
 ///
 /// * `puzzle` - A reference to the `Puzzle` for which constraints are generated.
 /// * `vars` - A reference to the `Variables` structure containing decision variables.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle.
 ///
 /// # Returns
 ///
-/// A vector of strings representing the mathematical constraints for the optimization model.
-fn bounds(puzzle: &Puzzle, vars: &Variables) -> Vec<String> {
+/// A vector of `LinearConstraint` representing the mathematical constraints for the optimization model.
+fn bounds(
+    puzzle: &Puzzle,
+    vars: &Variables,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Vec<LinearConstraint> {
     let mut prob_bounds = Vec::new();
 
-    prob_bounds.extend(vec!["y = 0".to_string()].into_iter());
-
     // Add constraints to ensure each tile is used only once.
-    prob_bounds.extend(each_tile_used_once_bound(vars));
+    prob_bounds.extend(each_tile_used_once_bound(vars, tileset));
 
     // Add constraints to ensure each position in the puzzle is filled.
     prob_bounds.extend(each_position_filled_bound(vars));
 
     // Add constraints to enforce adjacency rules.
-    prob_bounds.extend(next_adjacent_bound(puzzle, vars));
+    prob_bounds.extend(next_adjacent_bound(puzzle, vars, tileset.n(), topology));
 
     // Add constraints to ensure each position in the puzzle scheme is filled.
     prob_bounds.extend(partial_tiles_bound(puzzle, vars));
@@ -50,58 +55,52 @@ fn bounds(puzzle: &Puzzle, vars: &Variables) -> Vec<String> {
     prob_bounds
 }
 
-/// Computes a mathematical optimization model for the given puzzle and solution.
+/// Computes a mathematical optimization model for the given puzzle.
 ///
-/// The generated model follows the linear programming (LP) format, including:
-/// - **Objective Function:** Minimize the number of missing tiles.
+/// The generated model is built directly as a `ModelBuilder`, including:
+/// - **Objective Function:** A constant zero, any feasible assignment completes the puzzle.
 /// - **Constraints:** Ensure valid tile placement and adjacency.
 /// - **Binary Variables:** Representing the placement of tiles in the puzzle.
 ///
 /// # Arguments
 ///
 /// * `puzzle` - A reference to the `Puzzle` structure representing the puzzle configuration.
-/// * `solution` - A reference to the `Solution` structure representing the proposed solution.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle.
 ///
 /// # Returns
 ///
-/// * `Ok(String)` - A valid optimization model represented as a string.
-/// * `Err(DominoError::ModelError)` - If an error occurs while generating the model.
+/// * `Ok(ModelBuilder)` - The optimization model of the puzzle.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle has not a position for each tile of the set.
+pub fn compute_model(
+    puzzle: &Puzzle,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<ModelBuilder, DominoError> {
+    compute_model_with_variables(puzzle, tileset, topology).map(|(model, _)| model)
+}
+
+/// Computes the optimization model of a puzzle along with its decision variables, as `compute_model` does.
 ///
-/// # Errors
+/// # Returns
 ///
-/// This function returns an error if:
-/// - The variable generation fails, the puzzle is malformed.
-/// - There is an issue forming the constraints or objective function, the puzzle is malformed.
-pub fn compute_model(puzzle: &Puzzle) -> Result<String, DominoError> {
-    // Generate decision variables for the puzzle.
-    let prob_vars = variables(puzzle)?;
-    // println!("Variables: {:#?}", prob_vars);
-    // Compute the objective function to minimize missing tiles.
-    let prob_obj = "y".to_string();
+/// * `Ok((ModelBuilder, Variables))` - The model and the variables declared in it.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle has not a position for each tile of the set.
+pub fn compute_model_with_variables(
+    puzzle: &Puzzle,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<(ModelBuilder, Variables), DominoError> {
+    let mut model = ModelBuilder::new();
+
+    // Generate decision variables for the puzzle, declaring them as binary variables of the model.
+    let prob_vars = variables(puzzle, tileset, &mut model)?;
+    model.minimize(LinearExpression::new());
 
     // Generate constraints (bounds) for valid tile placement.
-    let prob_bounds = bounds(puzzle, &prob_vars);
-
-    // Construct the optimization model in LP format.
-    let mut model = "Minimize\n".to_string();
-    model.push_str(format!(" obj: {}\n", prob_obj).as_str());
-    model.push_str("Subject To\n");
-
-    // Append each constraint with a unique label.
-    for (i, constraint) in prob_bounds.iter().enumerate() {
-        if !constraint.is_empty() {
-            model.push_str(format!(" c{}: {}\n", i, constraint).as_str());
-        }
-    }
-
-    // Define binary decision variables for tile placement.
-    model.push_str("Binary\n");
-    for variable in prob_vars.vars {
-        model.push_str(format!(" {}\n", variable.label).as_str());
+    for constraint in bounds(puzzle, &prob_vars, tileset, topology) {
+        model.add_constraint(constraint);
     }
 
-    // Finalize the model.
-    model.push_str("End");
-
-    Ok(model)
+    Ok((model, prob_vars))
 }
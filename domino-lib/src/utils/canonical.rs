@@ -0,0 +1,327 @@
+//! This module provides the canonical form of a puzzle, shared by all the puzzles equivalent to it.
+//!
+//! The cycle of a puzzle can be started at any position and read in either direction, reading it backwards
+//! flipping every tile. An open line has fixed ends, so it can only be read in either direction.
+//! Optionally, the pip values can be relabelled as well. For even `n` the tileset contains every pair of
+//! values, so swapping two values everywhere yields another puzzle of the same size. For odd `n` it leaves out
+//! the tiles whose values differ by `(n+1)/2`, which a relabelling may bring in: puzzles are then compared by
+//! their pattern of values, even if only one of them is a puzzle of the tileset.
+
+use std::collections::HashMap;
+
+use super::{Puzzle, Tile, Topology};
+
+/// Selects which puzzles, or completions of a puzzle, are considered the same when comparing or counting them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleEquivalence {
+    /// Only identical puzzles are the same.
+    Exact,
+    /// Puzzles that are rotations of one another are the same.
+    Rotation,
+    /// Puzzles that are rotations or reflections of one another are the same.
+    #[default]
+    RotationAndReflection,
+    /// Puzzles that are rotations or reflections of one another, once their pip values are relabelled, are the same.
+    RotationReflectionAndRelabelling,
+}
+
+/// The canonical form of a puzzle, whose tiles compare and hash with their orientation.
+///
+/// The tiles of a `Puzzle` compare regardless of their orientation, so puzzles differing in the orientation of a
+/// tile are equal. Canonical forms are only equal for equivalent puzzles, which makes them suitable keys to
+/// deduplicate puzzles, e.g. in a `HashSet`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CanonicalPuzzle(Vec<Option<(i32, i32)>>);
+
+impl CanonicalPuzzle {
+    /// Returns the tiles of the canonical form as oriented pairs of values, `None` for the empty positions.
+    pub fn tiles(&self) -> &[Option<(i32, i32)>] {
+        &self.0
+    }
+
+    /// Returns the canonical form as a `Puzzle`, the representative of the puzzles equivalent to it.
+    pub fn to_puzzle(&self) -> Puzzle {
+        self.0
+            .iter()
+            .map(|tile| tile.map(Tile::from))
+            .collect::<Vec<Option<Tile>>>()
+            .into()
+    }
+}
+
+impl Puzzle {
+    /// Returns the canonical form of the puzzle up to rotation and reflection of its cycle.
+    ///
+    /// # Returns
+    ///
+    /// The `CanonicalPuzzle` shared by every rotation and reflection of this one.
+    pub fn canonical(&self) -> CanonicalPuzzle {
+        self.canonical_with(PuzzleEquivalence::default())
+    }
+
+    /// Returns the canonical form of the puzzle up to the given equivalence.
+    ///
+    /// The canonical form is the lexicographically smallest among the rotations of the puzzle and of its
+    /// reflection. When relabelling, each of them first has its pip values renumbered from 0 in the order
+    /// they appear in, so that every relabelling of a puzzle leads to the same form.
+    ///
+    /// # Arguments
+    ///
+    /// * `equivalence` - The `PuzzleEquivalence` defining the equivalent puzzles.
+    ///
+    /// # Returns
+    ///
+    /// The `CanonicalPuzzle` shared by every puzzle equivalent to this one.
+    pub fn canonical_with(&self, equivalence: PuzzleEquivalence) -> CanonicalPuzzle {
+        self.canonical_with_topology(equivalence, Topology::Closed)
+    }
+
+    /// Returns the canonical form of the puzzle up to the given equivalence, in the given topology.
+    ///
+    /// The puzzle of an open line is only compared to its reflection, as rotating it moves its ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `equivalence` - The `PuzzleEquivalence` defining the equivalent puzzles.
+    /// * `topology` - The `Topology` of the puzzle.
+    ///
+    /// # Returns
+    ///
+    /// The `CanonicalPuzzle` shared by every puzzle equivalent to this one.
+    pub fn canonical_with_topology(
+        &self,
+        equivalence: PuzzleEquivalence,
+        topology: Topology,
+    ) -> CanonicalPuzzle {
+        CanonicalPuzzle(canonical_key(self, equivalence, topology))
+    }
+
+    /// Returns whether the two puzzles are the same up to rotation and reflection of their cycle.
+    pub fn is_equivalent(&self, other: &Puzzle) -> bool {
+        self.is_equivalent_with(other, PuzzleEquivalence::default())
+    }
+
+    /// Returns whether the two puzzles are the same up to the given equivalence.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The `Puzzle` compared to this one.
+    /// * `equivalence` - The `PuzzleEquivalence` defining the equivalent puzzles.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the canonical forms of the puzzles, tile orientations included, are equal.
+    pub fn is_equivalent_with(&self, other: &Puzzle, equivalence: PuzzleEquivalence) -> bool {
+        self.is_equivalent_with_topology(other, equivalence, Topology::Closed)
+    }
+
+    /// Returns whether the two puzzles are the same up to the given equivalence, in the given topology.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The `Puzzle` compared to this one.
+    /// * `equivalence` - The `PuzzleEquivalence` defining the equivalent puzzles.
+    /// * `topology` - The `Topology` of both puzzles.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the canonical forms of the puzzles in the topology, tile orientations included, are equal.
+    pub fn is_equivalent_with_topology(
+        &self,
+        other: &Puzzle,
+        equivalence: PuzzleEquivalence,
+        topology: Topology,
+    ) -> bool {
+        self.len() == other.len()
+            && canonical_key(self, equivalence, topology)
+                == canonical_key(other, equivalence, topology)
+    }
+}
+
+/// Computes the canonical form of a puzzle as oriented pairs, which unlike `Tile` compare their orientation.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` to normalize.
+/// * `equivalence` - The `PuzzleEquivalence` defining the equivalent puzzles.
+/// * `topology` - The `Topology` of the puzzle, only a closed cycle can be rotated.
+///
+/// # Returns
+///
+/// The tiles of the canonical form of the puzzle.
+pub(crate) fn canonical_key(
+    puzzle: &Puzzle,
+    equivalence: PuzzleEquivalence,
+    topology: Topology,
+) -> Vec<Option<(i32, i32)>> {
+    let forward: Vec<Option<(i32, i32)>> = puzzle
+        .0
+        .iter()
+        .map(|tile| tile.map(|tile| (tile.0, tile.1)))
+        .collect();
+    let reflected: Vec<Option<(i32, i32)>> = forward
+        .iter()
+        .rev()
+        .map(|tile| tile.map(|(left, right)| (right, left)))
+        .collect();
+
+    let starts = match (equivalence, topology) {
+        (PuzzleEquivalence::Exact, _) | (_, Topology::Open) => forward.len().min(1),
+        _ => forward.len(),
+    };
+    let directions = match equivalence {
+        PuzzleEquivalence::Exact | PuzzleEquivalence::Rotation => 1,
+        _ => 2,
+    };
+    [forward, reflected]
+        .iter()
+        .take(directions)
+        .flat_map(|tiles| {
+            (0..starts).map(move |start| {
+                let rotation = tiles[start..].iter().chain(tiles[..start].iter()).copied();
+                match equivalence {
+                    PuzzleEquivalence::RotationReflectionAndRelabelling => relabel(rotation),
+                    _ => rotation.collect::<Vec<_>>(),
+                }
+            })
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+/// Renumbers the pip values from 0 in the order they first appear in the tiles.
+fn relabel(tiles: impl Iterator<Item = Option<(i32, i32)>>) -> Vec<Option<(i32, i32)>> {
+    let mut labels: HashMap<i32, i32> = HashMap::new();
+    let mut label = |value: i32| {
+        let next = labels.len() as i32;
+        *labels.entry(value).or_insert(next)
+    };
+    tiles
+        .map(|tile| tile.map(|(left, right)| (label(left), label(right))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{CanonicalPuzzle, PuzzleEquivalence};
+    use crate::{Puzzle, Topology};
+
+    fn puzzle() -> Puzzle {
+        vec![
+            Some((0, 0).into()),
+            Some((0, 1).into()),
+            None,
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            Some((2, 3).into()),
+            Some((3, 3).into()),
+            Some((3, 0).into()),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn test_canonical_rotation_and_reflection() {
+        let puzzle = puzzle();
+        let mut rotated = puzzle.clone();
+        rotated.0.rotate_left(3);
+        let reflected: Puzzle = puzzle
+            .0
+            .iter()
+            .rev()
+            .map(|tile| tile.map(|tile| tile.flip()))
+            .collect::<Vec<_>>()
+            .into();
+
+        assert!(puzzle.is_equivalent(&rotated));
+        assert!(puzzle.is_equivalent(&reflected));
+        assert_eq!(puzzle.canonical(), rotated.canonical());
+        assert_eq!(
+            rotated.canonical().to_puzzle().canonical(),
+            rotated.canonical()
+        );
+
+        let mut other = puzzle.clone();
+        other.0.swap(0, 4);
+        assert!(!puzzle.is_equivalent(&other));
+    }
+
+    #[test]
+    fn test_canonical_exact_and_rotation() {
+        let puzzle = puzzle();
+        let mut rotated = puzzle.clone();
+        rotated.0.rotate_left(3);
+        let reflected: Puzzle = puzzle
+            .0
+            .iter()
+            .rev()
+            .map(|tile| tile.map(|tile| tile.flip()))
+            .collect::<Vec<_>>()
+            .into();
+
+        assert!(puzzle.is_equivalent_with(&puzzle, PuzzleEquivalence::Exact));
+        assert!(!puzzle.is_equivalent_with(&rotated, PuzzleEquivalence::Exact));
+        assert!(puzzle.is_equivalent_with(&rotated, PuzzleEquivalence::Rotation));
+        assert!(!puzzle.is_equivalent_with(&reflected, PuzzleEquivalence::Rotation));
+    }
+
+    #[test]
+    fn test_canonical_relabelling() {
+        let puzzle = puzzle();
+        // Swap the values 1 and 3 everywhere
+        let swap = |value: i32| match value {
+            1 => 3,
+            3 => 1,
+            value => value,
+        };
+        let relabelled: Puzzle = puzzle
+            .0
+            .iter()
+            .map(|tile| tile.map(|tile| (swap(tile.0), swap(tile.1)).into()))
+            .collect::<Vec<_>>()
+            .into();
+
+        assert!(!puzzle.is_equivalent(&relabelled));
+        assert!(puzzle.is_equivalent_with(
+            &relabelled,
+            PuzzleEquivalence::RotationReflectionAndRelabelling
+        ));
+    }
+
+    #[test]
+    fn test_canonical_orientation() {
+        // Tiles compare regardless of their orientation, while canonical forms do not
+        let puzzle: Puzzle = vec![Some((0, 1).into()), None, Some((2, 3).into()), None].into();
+        let flipped: Puzzle = vec![Some((1, 0).into()), None, Some((2, 3).into()), None].into();
+        assert_eq!(puzzle, flipped);
+        assert!(!puzzle.is_equivalent(&flipped));
+        assert_ne!(puzzle.canonical(), flipped.canonical());
+        let forms: HashSet<CanonicalPuzzle> = [puzzle.canonical(), flipped.canonical()].into();
+        assert_eq!(forms.len(), 2);
+    }
+
+    #[test]
+    fn test_canonical_open() {
+        let puzzle = puzzle();
+        let mut rotated = puzzle.clone();
+        rotated.0.rotate_left(1);
+        let reflected: Puzzle = puzzle
+            .0
+            .iter()
+            .rev()
+            .map(|tile| tile.map(|tile| tile.flip()))
+            .collect::<Vec<_>>()
+            .into();
+
+        let equivalence = PuzzleEquivalence::default();
+        assert!(puzzle.is_equivalent(&rotated));
+        assert!(!puzzle.is_equivalent_with_topology(&rotated, equivalence, Topology::Open));
+        assert!(puzzle.is_equivalent_with_topology(&reflected, equivalence, Topology::Open));
+        assert_eq!(
+            puzzle.canonical_with_topology(equivalence, Topology::Open),
+            reflected.canonical_with_topology(equivalence, Topology::Open)
+        );
+    }
+}
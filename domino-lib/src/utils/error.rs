@@ -1,14 +1,23 @@
+use super::{ModelParseError, Tile};
+
 #[derive(Debug, PartialEq)]
 pub enum DominoError {
     InvalidLength,
     UnsolvablePuzzle,
     NotValidPuzzle,
+    AmbiguousPuzzle {
+        alternative: Vec<Option<Tile>>,
+        differing_positions: Vec<usize>,
+    },
+    MalformedPuzzle(String),
     Timeout,
     ModelGenerationError(String),
     ModelError(String),
     GenerationError(String),
     InvalidClass(String),
     EmptyPuzzle,
+    InvalidTileSet(String),
+    ModelParseError(ModelParseError),
 }
 
 impl std::fmt::Display for DominoError {
@@ -20,7 +29,16 @@ impl std::fmt::Display for DominoError {
                 f,
                 "The puzzle is not valid/unique, it has multiple solutions"
             ),
-            Self::Timeout => write!(f, "The puzzle took too long to solve"),
+            Self::AmbiguousPuzzle {
+                differing_positions,
+                ..
+            } => write!(
+                f,
+                "The puzzle is not unique, another solution differs at positions {:?}",
+                differing_positions
+            ),
+            Self::MalformedPuzzle(message) => write!(f, "The puzzle is malformed: {}", message),
+            Self::Timeout => write!(f, "The puzzle took too long to solve or generate"),
             Self::ModelGenerationError(message) => write!(f, "{}", message),
             Self::ModelError(message) => write!(f, "{}", message),
             Self::GenerationError(message) => write!(
@@ -30,6 +48,8 @@ impl std::fmt::Display for DominoError {
             ),
             Self::InvalidClass(message) => write!(f, "{}", message),
             Self::EmptyPuzzle => write!(f, "The puzzle is empty"),
+            Self::InvalidTileSet(message) => write!(f, "The tileset is not valid: {}", message),
+            Self::ModelParseError(error) => write!(f, "The model could not be parsed at {}", error),
         }
     }
 }
@@ -1,106 +1,112 @@
-use std::collections::HashMap;
+//! This module translates a `ModelBuilder` into a `good_lp` problem, for the backends running a `good_lp` solver.
 
 use good_lp::{
-    default_solver, variable, Constraint, Expression, ProblemVariables, ResolutionError,
-    SolverModel, Variable, VariableDefinition,
+    solvers::Solver, variable, Constraint, Expression, ProblemVariables, ResolutionError,
+    Solution, SolverModel, Variable,
 };
 
-use super::{
-    parser::{BoundType, ObjectiveType},
-    translator::ResultTranslator,
-    ModelParser,
-};
+use super::builder::{ConstraintSense, LinearExpression, ModelBuilder, ObjectiveType, VariableKind};
 
 pub struct Adapter {
     vars: ProblemVariables,
-    pub variable_map: HashMap<String, (VariableDefinition, Variable)>,
-    var_names: HashMap<Variable, String>,
+    variables: Vec<Variable>,
     objective_direction: ObjectiveType,
     objective: Expression,
     bounds: Vec<Constraint>,
 }
 
 impl Adapter {
-    pub fn new() -> Adapter {
-        Adapter {
+    /// Translates the variables, the objective and the constraints of the model.
+    pub fn new(model: &ModelBuilder) -> Adapter {
+        let mut adapter = Adapter {
             vars: ProblemVariables::new(),
-            variable_map: HashMap::new(),
-            var_names: HashMap::new(),
+            variables: Vec::new(),
             objective_direction: ObjectiveType::Minimize,
             objective: Expression::from_other_affine(0.0),
             bounds: Vec::new(),
-        }
+        };
+        adapter.variables = adapter.adapt_variables(model);
+        adapter.adapt_objective(model);
+        adapter.adapt_bounds(model);
+        adapter
     }
 
-    pub fn adapt(mut self, parser: &ModelParser) -> Result<ResultTranslator, ResolutionError> {
-        self.adapt_variables(parser);
-        self.adapt_objective(parser);
-        self.adapt_bounds(parser);
-        let mut problem = if self.objective_direction == ObjectiveType::Minimize {
-            self.vars.minimise(self.objective.clone())
+    /// Solves the translated model with the given `good_lp` solver.
+    ///
+    /// # Arguments
+    ///
+    /// * `solver` - The `good_lp` solver executing the problem.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<f64>)` - The value of each variable of the model, indexed by `VariableId::index`.
+    /// * `Err(ResolutionError)` - If the problem is infeasible, unbounded or the solver fails.
+    pub fn solve_using<S: Solver>(self, solver: S) -> Result<Vec<f64>, ResolutionError>
+    where
+        S::Model: SolverModel<Error = ResolutionError>,
+    {
+        let problem = if self.objective_direction == ObjectiveType::Minimize {
+            self.vars.minimise(self.objective)
         } else {
-            self.vars.maximise(self.objective.clone())
+            self.vars.maximise(self.objective)
+        };
+        let mut model = problem.using(solver);
+        for constraint in self.bounds {
+            model.add_constraint(constraint);
         }
-        .using(default_solver);
-        self.bounds.into_iter().for_each(|constraint| {
-            problem.add_constraint(constraint);
-        });
-        problem.solve().map(|solution| {
-            ResultTranslator::new(
-                self.objective,
-                self.var_names,
-                self.variable_map.clone(),
-                solution,
-            )
-        })
+        let solution = model.solve()?;
+        Ok(self
+            .variables
+            .iter()
+            .map(|variable| solution.value(*variable))
+            .collect())
     }
 
-    fn adapt_variables(&mut self, parser: &ModelParser) {
-        for variable_name in parser.binary_variables.clone() {
-            let def_variable = variable().binary().name(variable_name.clone());
-            let variable = self.vars.add(def_variable.clone());
-            self.variable_map
-                .insert(variable_name.clone(), (def_variable, variable));
-            self.var_names.insert(variable, variable_name);
-        }
+    /// Declares the variables of the model, returning the `good_lp` variable of each `VariableId` index.
+    ///
+    /// Every variable lies within its bounds in the model, intersected with `[0, 1]` for binary ones.
+    fn adapt_variables(&mut self, model: &ModelBuilder) -> Vec<Variable> {
+        model
+            .variables()
+            .map(|id| {
+                let (lower, upper) = model.variable_bounds(id);
+                let def_variable = match model.variable_kind(id) {
+                    VariableKind::Binary => {
+                        variable().binary().min(lower.max(0.0)).max(upper.min(1.0))
+                    }
+                    VariableKind::Integer => variable().integer().min(lower).max(upper),
+                    VariableKind::Continuous => variable().min(lower).max(upper),
+                }
+                .name(model.variable_name(id));
+                self.vars.add(def_variable)
+            })
+            .collect()
     }
 
-    fn adapt_objective(&mut self, parser: &ModelParser) {
-        let objective = parser.objective.clone();
-        self.objective_direction = objective.objective_type;
-        self.objective = Expression::from_other_affine(0.0);
-        for term in objective.terms.0 {
-            if let Some(variable) = self.variable_map.get(&term.1) {
-                self.objective
-                    .add_mul(term.0, Expression::from_other_affine(variable.1));
-            }
-        }
-        self.objective += objective.terms.1;
+    fn adapt_objective(&mut self, model: &ModelBuilder) {
+        self.objective_direction = model.objective_type();
+        self.objective = Self::expression(model.objective(), &self.variables);
     }
 
-    fn adapt_bounds(&mut self, parser: &ModelParser) {
-        for bound in &parser.bounds {
-            let mut lhs_expr = Expression::from_other_affine(0.0);
-            bound.lhs.0.iter().for_each(|(coef, var_name)| {
-                if let Some(variable) = self.variable_map.get(var_name) {
-                    lhs_expr.add_mul(*coef, variable.1);
-                }
-            });
-            lhs_expr += bound.lhs.1;
-            let mut rhs_expr = Expression::from_other_affine(0.0);
-            bound.rhs.0.iter().for_each(|(coef, var_name)| {
-                if let Some(variable) = self.variable_map.get(var_name) {
-                    rhs_expr.add_mul(*coef, variable.1);
-                }
-            });
-            rhs_expr += bound.rhs.1;
+    fn adapt_bounds(&mut self, model: &ModelBuilder) {
+        for bound in model.constraints() {
+            let lhs_expr = Self::expression(bound.expression(), &self.variables);
+            let rhs_expr = Expression::from_other_affine(bound.rhs());
 
-            let constraint = match bound.bound_type {
-                BoundType::Equality => lhs_expr.eq(rhs_expr),
-                BoundType::LessThanOrEqual => lhs_expr.leq(rhs_expr),
-                BoundType::GreaterThanOrEqual => lhs_expr.geq(rhs_expr),
+            let constraint = match bound.sense() {
+                ConstraintSense::Equal => lhs_expr.eq(rhs_expr),
+                ConstraintSense::LessOrEqual => lhs_expr.leq(rhs_expr),
+                ConstraintSense::GreaterOrEqual => lhs_expr.geq(rhs_expr),
             };
             self.bounds.push(constraint);
         }
     }
+
+    fn expression(expression: &LinearExpression, variables: &[Variable]) -> Expression {
+        let mut result = Expression::from_other_affine(expression.constant());
+        for (coefficient, id) in expression.terms() {
+            result.add_mul(*coefficient, variables[id.index()]);
+        }
+        result
+    }
 }
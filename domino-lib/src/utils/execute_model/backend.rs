@@ -0,0 +1,76 @@
+//! This module defines the solver backends able to execute the models of a `ModelBuilder`.
+//!
+//! A backend receives the typed model and reports the value of each of its variables, so any engine can
+//! implement it. The provided backends translate the model to `good_lp` through the `Adapter`, and each is
+//! compiled only when its cargo feature is enabled:
+//! - `highs`: the HiGHS MILP solver (enabled by default).
+//! - `microlp`: a pure-Rust MILP solver, useful on targets where compiling HiGHS is painful.
+//!
+//! At least one of them must be enabled; when both are available, `default_backend` prefers HiGHS.
+
+use good_lp::ResolutionError;
+
+use super::{adapter::Adapter, builder::ModelBuilder};
+
+/// A solver able to execute a model and report the value of its variables.
+///
+/// Implementing this trait is enough to plug a new solver into `Model::execute_with`.
+pub trait SolverBackend {
+    /// Returns the human readable name of the backend.
+    fn name(&self) -> &'static str;
+
+    /// Solves the given model.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The `ModelBuilder` holding the variables, the objective and the constraints.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<f64>)` - The value of each variable of the model, indexed by `VariableId::index`.
+    /// * `Err(ResolutionError)` - If the model is infeasible, unbounded or the solver fails.
+    fn solve(&self, model: &ModelBuilder) -> Result<Vec<f64>, ResolutionError>;
+}
+
+/// Backend running the models through the HiGHS solver.
+#[cfg(feature = "highs")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HighsBackend;
+
+#[cfg(feature = "highs")]
+impl SolverBackend for HighsBackend {
+    fn name(&self) -> &'static str {
+        "HiGHS"
+    }
+
+    fn solve(&self, model: &ModelBuilder) -> Result<Vec<f64>, ResolutionError> {
+        Adapter::new(model).solve_using(good_lp::highs)
+    }
+}
+
+/// Backend running the models through the pure-Rust microlp solver.
+#[cfg(feature = "microlp")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MicroLpBackend;
+
+#[cfg(feature = "microlp")]
+impl SolverBackend for MicroLpBackend {
+    fn name(&self) -> &'static str {
+        "microlp"
+    }
+
+    fn solve(&self, model: &ModelBuilder) -> Result<Vec<f64>, ResolutionError> {
+        Adapter::new(model).solve_using(good_lp::microlp)
+    }
+}
+
+/// Returns the backend selected through the cargo features, preferring HiGHS when both are enabled.
+#[cfg(feature = "highs")]
+pub fn default_backend() -> &'static dyn SolverBackend {
+    &HighsBackend
+}
+
+#[cfg(not(feature = "highs"))]
+pub fn default_backend() -> &'static dyn SolverBackend {
+    &MicroLpBackend
+}
@@ -0,0 +1,371 @@
+//! This module defines the `ModelBuilder`, a typed in-memory description of a linear model.
+//!
+//! The solve and validate modules declare their variables, constraints and objective directly on a
+//! `ModelBuilder`, which the `Adapter` turns into `good_lp` expressions without going through the LP text.
+//! A `ModelBuilder` can still be written to and read from the CPLEX LP and MPS formats, to inspect it with other tools.
+
+use std::collections::HashMap;
+
+use super::mps::{write_mps, MpsParser};
+use super::parser::{ModelParseError, ModelParser};
+use super::writer::write_lp;
+
+/// The direction in which the objective of a model is optimized.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectiveType {
+    #[default]
+    Minimize,
+    Maximize,
+}
+
+/// The relation between the expression of a constraint and its right-hand side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintSense {
+    Equal,
+    LessOrEqual,
+    GreaterOrEqual,
+}
+
+/// The domain of a variable of a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariableKind {
+    Continuous,
+    Integer,
+    Binary,
+}
+
+/// The handle of a variable declared in a `ModelBuilder`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VariableId(usize);
+
+impl VariableId {
+    /// Returns the position of the variable in the declaration order of its model.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// A linear combination of variables plus a constant.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LinearExpression {
+    terms: Vec<(f64, VariableId)>,
+    constant: f64,
+}
+
+impl LinearExpression {
+    /// Creates the empty expression, equal to zero.
+    pub fn new() -> Self {
+        LinearExpression::default()
+    }
+
+    /// Creates the sum of the given variables, each with coefficient one.
+    pub fn sum(variables: impl IntoIterator<Item = VariableId>) -> Self {
+        LinearExpression {
+            terms: variables
+                .into_iter()
+                .map(|variable| (1.0, variable))
+                .collect(),
+            constant: 0.0,
+        }
+    }
+
+    /// Adds `coefficient * variable` to the expression.
+    pub fn add_term(&mut self, coefficient: f64, variable: VariableId) {
+        self.terms.push((coefficient, variable));
+    }
+
+    /// Adds `constant` to the constant of the expression.
+    pub fn add_constant(&mut self, constant: f64) {
+        self.constant += constant;
+    }
+
+    /// Returns the expression with `coefficient * variable` added.
+    pub fn with_term(mut self, coefficient: f64, variable: VariableId) -> Self {
+        self.add_term(coefficient, variable);
+        self
+    }
+
+    /// Returns the `(coefficient, variable)` terms of the expression, in insertion order.
+    pub fn terms(&self) -> &[(f64, VariableId)] {
+        &self.terms
+    }
+
+    /// Returns the constant of the expression.
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
+
+    /// Returns whether the expression has no variable term.
+    pub fn is_constant(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+/// A linear constraint `expression sense rhs`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearConstraint {
+    name: String,
+    expression: LinearExpression,
+    sense: ConstraintSense,
+    rhs: f64,
+}
+
+impl LinearConstraint {
+    /// Creates a new unnamed constraint, named after its position when added to a `ModelBuilder`.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - The left-hand side of the constraint.
+    /// * `sense` - The `ConstraintSense` relating both sides.
+    /// * `rhs` - The constant right-hand side of the constraint.
+    pub fn new(expression: LinearExpression, sense: ConstraintSense, rhs: f64) -> Self {
+        LinearConstraint {
+            name: String::new(),
+            expression,
+            sense,
+            rhs,
+        }
+    }
+
+    /// Sets the name of the constraint.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Returns the name of the constraint.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the left-hand side of the constraint.
+    pub fn expression(&self) -> &LinearExpression {
+        &self.expression
+    }
+
+    /// Returns the relation between both sides of the constraint.
+    pub fn sense(&self) -> ConstraintSense {
+        self.sense
+    }
+
+    /// Returns the constant right-hand side of the constraint.
+    pub fn rhs(&self) -> f64 {
+        self.rhs
+    }
+}
+
+/// A linear model under construction: its variables, constraints and objective.
+///
+/// Variables are declared once and then referenced through their `VariableId`, so that building the
+/// constraints never formats nor looks up variable names.
+#[derive(Debug, Default, Clone)]
+pub struct ModelBuilder {
+    names: Vec<String>,
+    kinds: Vec<VariableKind>,
+    bounds: Vec<(f64, f64)>,
+    by_name: HashMap<String, VariableId>,
+    constraints: Vec<LinearConstraint>,
+    objective_type: ObjectiveType,
+    objective: LinearExpression,
+}
+
+impl ModelBuilder {
+    /// Creates an empty model, minimizing a zero objective.
+    pub fn new() -> Self {
+        ModelBuilder::default()
+    }
+
+    /// Reads a model written in the CPLEX LP format.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The model in LP format.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ModelBuilder)` - The model described by the text.
+    /// * `Err(ModelParseError)` - The line, column and cause of the first error of the text.
+    pub fn from_lp(text: &str) -> Result<ModelBuilder, ModelParseError> {
+        ModelParser::parse(text)
+    }
+
+    /// Writes the model in the CPLEX LP format, readable by `ModelBuilder::from_lp` and external MILP tools.
+    pub fn to_lp(&self) -> String {
+        write_lp(self)
+    }
+
+    /// Reads a model written in the free MPS format.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The model in MPS format.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ModelBuilder)` - The model described by the text.
+    /// * `Err(ModelParseError)` - The line, column and cause of the first error of the text.
+    pub fn from_mps(text: &str) -> Result<ModelBuilder, ModelParseError> {
+        MpsParser::parse(text)
+    }
+
+    /// Writes the model in the free MPS format, readable by `ModelBuilder::from_mps` and external MILP tools.
+    pub fn to_mps(&self) -> String {
+        write_mps(self)
+    }
+
+    /// Declares a binary variable, or returns the existing variable with the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable, unique in the model.
+    ///
+    /// # Returns
+    ///
+    /// The `VariableId` referencing the variable in expressions.
+    pub fn add_binary(&mut self, name: impl Into<String>) -> VariableId {
+        self.add_variable(name, VariableKind::Binary)
+    }
+
+    /// Declares a variable of the given kind, or returns the existing variable with the same name.
+    ///
+    /// Binary variables lie in `[0, 1]`, the other ones in `[0, +inf)` until `set_bounds` changes them.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable, unique in the model.
+    /// * `kind` - The `VariableKind` of a new variable, ignored if the variable exists.
+    ///
+    /// # Returns
+    ///
+    /// The `VariableId` referencing the variable in expressions.
+    pub fn add_variable(&mut self, name: impl Into<String>, kind: VariableKind) -> VariableId {
+        let name = name.into();
+        if let Some(&variable) = self.by_name.get(&name) {
+            return variable;
+        }
+        let variable = VariableId(self.names.len());
+        self.by_name.insert(name.clone(), variable);
+        self.names.push(name);
+        self.kinds.push(kind);
+        self.bounds.push(match kind {
+            VariableKind::Binary => (0.0, 1.0),
+            _ => (0.0, f64::INFINITY),
+        });
+        variable
+    }
+
+    /// Changes the kind of a variable, keeping its bounds.
+    pub fn set_kind(&mut self, variable: VariableId, kind: VariableKind) {
+        self.kinds[variable.0] = kind;
+    }
+
+    /// Sets the lower and upper bounds of a variable, infinite bounds leaving it unbounded on that side.
+    pub fn set_bounds(&mut self, variable: VariableId, lower: f64, upper: f64) {
+        self.bounds[variable.0] = (lower, upper);
+    }
+
+    /// Adds a constraint to the model, naming it `c<index>` if it has no name.
+    pub fn add_constraint(&mut self, constraint: LinearConstraint) {
+        let constraint = if constraint.name.is_empty() {
+            let name = format!("c{}", self.constraints.len());
+            constraint.with_name(name)
+        } else {
+            constraint
+        };
+        self.constraints.push(constraint);
+    }
+
+    /// Sets the objective of the model, minimizing the given expression.
+    pub fn minimize(&mut self, objective: LinearExpression) {
+        self.objective_type = ObjectiveType::Minimize;
+        self.objective = objective;
+    }
+
+    /// Sets the objective of the model, maximizing the given expression.
+    pub fn maximize(&mut self, objective: LinearExpression) {
+        self.objective_type = ObjectiveType::Maximize;
+        self.objective = objective;
+    }
+
+    /// Returns the variable declared with the given name, if any.
+    pub fn variable(&self, name: &str) -> Option<VariableId> {
+        self.by_name.get(name).copied()
+    }
+
+    /// Returns the name of a variable of the model.
+    pub fn variable_name(&self, variable: VariableId) -> &str {
+        &self.names[variable.0]
+    }
+
+    /// Returns the kind of a variable of the model.
+    pub fn variable_kind(&self, variable: VariableId) -> VariableKind {
+        self.kinds[variable.0]
+    }
+
+    /// Returns the `(lower, upper)` bounds of a variable of the model.
+    pub fn variable_bounds(&self, variable: VariableId) -> (f64, f64) {
+        self.bounds[variable.0]
+    }
+
+    /// Returns the variables of the model, in declaration order.
+    pub fn variables(&self) -> impl Iterator<Item = VariableId> + '_ {
+        (0..self.names.len()).map(VariableId)
+    }
+
+    /// Returns the number of variables of the model.
+    pub fn variable_count(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns the constraints of the model, in insertion order.
+    pub fn constraints(&self) -> &[LinearConstraint] {
+        &self.constraints
+    }
+
+    /// Returns the direction in which the objective is optimized.
+    pub fn objective_type(&self) -> ObjectiveType {
+        self.objective_type
+    }
+
+    /// Returns the objective of the model.
+    pub fn objective(&self) -> &LinearExpression {
+        &self.objective
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConstraintSense, LinearConstraint, LinearExpression, ModelBuilder};
+
+    #[test]
+    fn test_builder_variables_and_constraints() {
+        let mut model = ModelBuilder::new();
+        let x = model.add_binary("x");
+        let y = model.add_binary("y");
+        assert_eq!(model.add_binary("x"), x);
+        assert_eq!(model.variable("y"), Some(y));
+        assert_eq!(model.variable_name(y), "y");
+        assert_eq!(model.variable_count(), 2);
+
+        model.add_constraint(LinearConstraint::new(
+            LinearExpression::sum([x, y]),
+            ConstraintSense::Equal,
+            1.0,
+        ));
+        model.add_constraint(
+            LinearConstraint::new(
+                LinearExpression::new().with_term(2.0, x),
+                ConstraintSense::LessOrEqual,
+                1.0,
+            )
+            .with_name("limit"),
+        );
+        let names: Vec<&str> = model
+            .constraints()
+            .iter()
+            .map(|constraint| constraint.name())
+            .collect();
+        assert_eq!(names, vec!["c0", "limit"]);
+        assert_eq!(model.constraints()[1].expression().terms(), &[(2.0, x)]);
+    }
+}
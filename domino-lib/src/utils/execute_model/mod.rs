@@ -1,20 +1,277 @@
 mod adapter;
+mod backend;
+mod builder;
+mod mps;
 mod parser;
 mod translator;
+mod writer;
 
-use adapter::Adapter;
+#[cfg(feature = "highs")]
+pub use backend::HighsBackend;
+#[cfg(feature = "microlp")]
+pub use backend::MicroLpBackend;
+pub use backend::{default_backend, SolverBackend};
+pub use builder::{
+    ConstraintSense, LinearConstraint, LinearExpression, ModelBuilder, ObjectiveType, VariableId,
+    VariableKind,
+};
 use good_lp::ResolutionError;
+
+use super::DominoError;
+pub use parser::ModelParseError;
 use parser::ModelParser;
 pub use translator::ResultTranslator;
 
 pub struct Model {}
 
 impl Model {
-    pub fn execute(model: String) -> Result<ResultTranslator, ResolutionError> {
-        let mut parser = ModelParser::new();
-        parser.parse(&model);
-        let adapter = Adapter::new();
-        let solved_problem = Adapter::adapt(adapter, &parser);
-        solved_problem
+    /// Executes the model with the backend selected through the cargo features.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model in LP format.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ResultTranslator)` - The solved model.
+    /// * `Err(DominoError::ModelParseError)` - If the text is not a valid LP model.
+    /// * `Err(DominoError::ModelError)` - If the model has no solution or the solver fails.
+    pub fn execute(model: String) -> Result<ResultTranslator, DominoError> {
+        Self::execute_with(model, default_backend())
+    }
+
+    /// Executes the model with the given backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model in LP format.
+    /// * `backend` - The `SolverBackend` used to solve the model.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ResultTranslator)` - The solved model.
+    /// * `Err(DominoError)` - If an error occurs, as for `Model::execute`.
+    pub fn execute_with(
+        model: String,
+        backend: &dyn SolverBackend,
+    ) -> Result<ResultTranslator, DominoError> {
+        let model = ModelParser::parse(&model).map_err(DominoError::ModelParseError)?;
+        Self::execute_builder_with(&model, backend)
+            .map_err(|error| DominoError::ModelError(error.to_string()))
+    }
+
+    /// Executes a model written in the free MPS format with the backend selected through the cargo features.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model in MPS format.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ResultTranslator)` - The solved model.
+    /// * `Err(DominoError::ModelParseError)` - If the text is not a valid MPS model.
+    /// * `Err(DominoError::ModelError)` - If the model has no solution or the solver fails.
+    pub fn execute_mps(model: String) -> Result<ResultTranslator, DominoError> {
+        Self::execute_mps_with(model, default_backend())
+    }
+
+    /// Executes a model written in the free MPS format with the given backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model in MPS format.
+    /// * `backend` - The `SolverBackend` used to solve the model.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ResultTranslator)` - The solved model.
+    /// * `Err(DominoError)` - If an error occurs, as for `Model::execute_mps`.
+    pub fn execute_mps_with(
+        model: String,
+        backend: &dyn SolverBackend,
+    ) -> Result<ResultTranslator, DominoError> {
+        let model = ModelBuilder::from_mps(&model).map_err(DominoError::ModelParseError)?;
+        Self::execute_builder_with(&model, backend)
+            .map_err(|error| DominoError::ModelError(error.to_string()))
+    }
+
+    /// Executes a typed model with the backend selected through the cargo features.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The `ModelBuilder` describing the model.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ResultTranslator)` - The solved model.
+    /// * `Err(ResolutionError)` - If the model has no solution.
+    pub fn execute_builder(model: &ModelBuilder) -> Result<ResultTranslator, ResolutionError> {
+        Self::execute_builder_with(model, default_backend())
+    }
+
+    /// Executes a typed model with the given backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The `ModelBuilder` describing the model.
+    /// * `backend` - The `SolverBackend` used to solve the model.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ResultTranslator)` - The solved model.
+    /// * `Err(ResolutionError)` - If the model has no solution.
+    pub fn execute_builder_with(
+        model: &ModelBuilder,
+        backend: &dyn SolverBackend,
+    ) -> Result<ResultTranslator, ResolutionError> {
+        let mut values = backend.solve(model)?;
+        if values.len() != model.variable_count() {
+            return Err(ResolutionError::Str(format!(
+                "the backend {} reported {} values for {} variables",
+                backend.name(),
+                values.len(),
+                model.variable_count()
+            )));
+        }
+        // Backends report integer and binary variables within their own tolerance,
+        // rounding gives back the exact values.
+        for variable in model.variables() {
+            if model.variable_kind(variable) != VariableKind::Continuous {
+                values[variable.index()] = values[variable.index()].round();
+            }
+        }
+        Ok(ResultTranslator::new(model, values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use good_lp::ResolutionError;
+
+    use super::{
+        default_backend, ConstraintSense, DominoError, LinearConstraint, LinearExpression, Model,
+        ModelBuilder, SolverBackend, VariableKind,
+    };
+
+    /// A backend reporting fixed values, without running any solver.
+    struct FixedBackend(Vec<f64>);
+
+    impl SolverBackend for FixedBackend {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn solve(&self, _model: &ModelBuilder) -> Result<Vec<f64>, ResolutionError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn test_execute_with_default_backend() {
+        let model = "Minimize\n obj: x1 + x2\nSubject To\n c0: x1 + x2 >= 1\nBinary\n x1\n x2\nEnd";
+        let translator = Model::execute_with(model.to_string(), default_backend()).unwrap();
+        assert_eq!(translator.get_objective(), 1.0);
+        let variables = translator._get_variables();
+        assert_eq!(variables["x1"] + variables["x2"], 1.0);
+    }
+
+    #[test]
+    fn test_execute_builder_matches_text() {
+        let mut model = ModelBuilder::new();
+        let x1 = model.add_binary("x1");
+        let x2 = model.add_binary("x2");
+        model.minimize(LinearExpression::sum([x1, x2]));
+        model.add_constraint(LinearConstraint::new(
+            LinearExpression::sum([x1, x2]),
+            ConstraintSense::GreaterOrEqual,
+            1.0,
+        ));
+        let translator = Model::execute_builder(&model).unwrap();
+        assert_eq!(translator.get_objective(), 1.0);
+        assert_eq!(
+            translator.value(x1).unwrap() + translator.value(x2).unwrap(),
+            1.0
+        );
+
+        let model = "Minimize\n obj: x1 + x2\nSubject To\n c0: x1 >= 1 - x2\nBinary\n x1\n x2\nEnd";
+        let translator = Model::execute(model.to_string()).unwrap();
+        assert_eq!(translator.get_objective(), 1.0);
+    }
+
+    #[test]
+    fn test_execute_builder_with_custom_backend() {
+        let mut model = ModelBuilder::new();
+        let x = model.add_binary("x");
+        let y = model.add_variable("y", VariableKind::Integer);
+        let z = model.add_variable("z", VariableKind::Continuous);
+        model.maximize(LinearExpression::sum([x, y]).with_term(0.5, z));
+
+        let backend = FixedBackend(vec![0.999_999, 2.000_001, 0.25]);
+        let translator = Model::execute_builder_with(&model, &backend).unwrap();
+        assert_eq!(translator.value(x), Some(1.0));
+        assert_eq!(translator.value(y), Some(2.0));
+        assert_eq!(translator.value(z), Some(0.25));
+        assert_eq!(translator.get_objective(), 3.125);
+
+        let backend = FixedBackend(vec![1.0]);
+        assert!(Model::execute_builder_with(&model, &backend).is_err());
+    }
+
+    #[test]
+    fn test_execute_mps_matches_text() {
+        let text =
+            "Maximize\n obj: x1 + 2 x2\nSubject To\n c0: x1 + x2 <= 1\nBinary\n x1\n x2\nEnd";
+        let mps = ModelBuilder::from_lp(text).unwrap().to_mps();
+        let translator = Model::execute_mps(mps).unwrap();
+        assert_eq!(translator.get_objective(), 2.0);
+        assert_eq!(translator._get_variables()["x2"], 1.0);
+        assert!(
+            Model::execute_mps("ROWS\n N  obj\nCOLUMNS\n    x  c0  1\nENDATA".to_string()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_execute_general_and_continuous() {
+        let model = "Maximize\n obj: 3 x + 2 y + z\n\
+                     Subject To\n c0: x + y + z <= 7.5\n c1: x - y >= 0.5\n c2: 2 x <= 5\n\
+                     Bounds\n 0 <= x <= 3\n y <= 2.5\n -1 <= z <= 1\n\
+                     General\n x\n\
+                     End";
+        let translator = Model::execute(model.to_string()).unwrap();
+        let variables = translator._get_variables();
+        assert_eq!(variables["x"], 2.0);
+        assert!((variables["y"] - 1.5).abs() < 1e-6);
+        assert!((variables["z"] - 1.0).abs() < 1e-6);
+        assert!((translator.get_objective() - 10.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_execute_fixed_binary() {
+        let model = "Maximize\n obj: x + y\n\
+                     Subject To\n c0: x + y <= 2\n\
+                     Bounds\n x = 0\n\
+                     Binary\n x\n y\n\
+                     End";
+        let translator = Model::execute(model.to_string()).unwrap();
+        let variables = translator._get_variables();
+        assert_eq!(variables["x"], 0.0);
+        assert_eq!(variables["y"], 1.0);
+    }
+
+    #[test]
+    fn test_execute_reports_errors() {
+        let model = "Minimize\n obj: x\nSubject To\n c0: x + * y >= 1\nBinary\n x\nEnd";
+        match Model::execute(model.to_string()) {
+            Err(DominoError::ModelParseError(error)) => {
+                assert_eq!((error.line(), error.column()), (4, 10));
+            }
+            _ => panic!("expected a parse error"),
+        }
+
+        let model = "Minimize\n obj: x\nSubject To\n c0: x >= 2\nBinary\n x\nEnd";
+        assert!(matches!(
+            Model::execute(model.to_string()),
+            Err(DominoError::ModelError(_))
+        ));
     }
 }
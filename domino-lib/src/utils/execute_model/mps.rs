@@ -0,0 +1,509 @@
+//! This module reads and writes models in the free MPS format.
+//!
+//! Fields are separated by whitespace, section headers start on the first column and lines starting
+//! with `*` are comments. Integer columns are enclosed in `INTORG`/`INTEND` markers, binary columns
+//! also get a `BV` bound, followed by `FX` when fixed. The constant of the objective is written as the opposite of its right-hand side.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::builder::{
+    ConstraintSense, LinearConstraint, LinearExpression, ModelBuilder, ObjectiveType, VariableKind,
+};
+use super::parser::ModelParseError;
+
+/// Writes a model in the free MPS format.
+///
+/// Columns are written in declaration order, so reading the text back declares the variables in the
+/// same order. Variables absent from every row get a zero objective coefficient to be declared.
+///
+/// # Arguments
+///
+/// * `model` - The `ModelBuilder` to write.
+///
+/// # Returns
+///
+/// The text of the model in MPS format.
+pub fn write_mps(model: &ModelBuilder) -> String {
+    let mut objective_row = String::from("OBJ");
+    while model
+        .constraints()
+        .iter()
+        .any(|constraint| constraint.name() == objective_row)
+    {
+        objective_row.push('_');
+    }
+
+    let mut text = String::from("NAME          MODEL\n");
+    if model.objective_type() == ObjectiveType::Maximize {
+        text.push_str("OBJSENSE\n    MAX\n");
+    }
+
+    text.push_str("ROWS\n");
+    let _ = writeln!(text, " N  {}", objective_row);
+    for constraint in model.constraints() {
+        let sense = match constraint.sense() {
+            ConstraintSense::Equal => "E",
+            ConstraintSense::LessOrEqual => "L",
+            ConstraintSense::GreaterOrEqual => "G",
+        };
+        let _ = writeln!(text, " {}  {}", sense, constraint.name());
+    }
+
+    // The coefficients of each column by row, repeated terms of a row being summed
+    let mut columns: Vec<Vec<(&str, f64)>> = vec![Vec::new(); model.variable_count()];
+    let rows = std::iter::once((objective_row.as_str(), model.objective())).chain(
+        model
+            .constraints()
+            .iter()
+            .map(|constraint| (constraint.name(), constraint.expression())),
+    );
+    for (row, expression) in rows {
+        for (coefficient, variable) in expression.terms() {
+            let column = &mut columns[variable.index()];
+            match column.iter_mut().find(|(name, _)| *name == row) {
+                Some(entry) => entry.1 += coefficient,
+                None => column.push((row, *coefficient)),
+            }
+        }
+    }
+
+    text.push_str("COLUMNS\n");
+    let mut integer = false;
+    for variable in model.variables() {
+        let is_integer = model.variable_kind(variable) != VariableKind::Continuous;
+        if is_integer != integer {
+            let marker = if is_integer { "'INTORG'" } else { "'INTEND'" };
+            let _ = writeln!(text, "    MARKER    'MARKER'    {}", marker);
+            integer = is_integer;
+        }
+        let name = model.variable_name(variable);
+        let column = &columns[variable.index()];
+        if column.is_empty() {
+            let _ = writeln!(text, "    {}  {}  0", name, objective_row);
+        }
+        for (row, coefficient) in column {
+            let _ = writeln!(text, "    {}  {}  {}", name, row, coefficient);
+        }
+    }
+    if integer {
+        text.push_str("    MARKER    'MARKER'    'INTEND'\n");
+    }
+
+    let rhs: Vec<(&str, f64)> =
+        std::iter::once((objective_row.as_str(), -model.objective().constant()))
+            .chain(
+                model
+                    .constraints()
+                    .iter()
+                    .map(|constraint| (constraint.name(), constraint.rhs())),
+            )
+            .filter(|(_, value)| *value != 0.0)
+            .collect();
+    if !rhs.is_empty() {
+        text.push_str("RHS\n");
+        for (row, value) in rhs {
+            let _ = writeln!(text, "    RHS  {}  {}", row, value);
+        }
+    }
+
+    let mut bounds = Vec::new();
+    for variable in model.variables() {
+        let name = model.variable_name(variable);
+        let (lower, upper) = model.variable_bounds(variable);
+        if model.variable_kind(variable) == VariableKind::Binary {
+            bounds.push(format!(" BV BND  {}", name));
+            // A binary variable fixed to one of its values keeps that bound after `BV`
+            if lower == upper {
+                bounds.push(format!(" FX BND  {}  {}", name, lower));
+            }
+        } else if lower == upper {
+            bounds.push(format!(" FX BND  {}  {}", name, lower));
+        } else if lower == f64::NEG_INFINITY && upper == f64::INFINITY {
+            bounds.push(format!(" FR BND  {}", name));
+        } else {
+            if lower == f64::NEG_INFINITY {
+                bounds.push(format!(" MI BND  {}", name));
+            } else if lower != 0.0 || upper < 0.0 {
+                // A negative upper bound alone would make the lower bound infinite
+                bounds.push(format!(" LO BND  {}  {}", name, lower));
+            }
+            if upper != f64::INFINITY {
+                bounds.push(format!(" UP BND  {}  {}", name, upper));
+            }
+        }
+    }
+    if !bounds.is_empty() {
+        text.push_str("BOUNDS\n");
+        for bound in bounds {
+            text.push_str(&bound);
+            text.push('\n');
+        }
+    }
+
+    text.push_str("ENDATA\n");
+    text
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Name,
+    ObjectiveSense,
+    Rows,
+    Columns,
+    Rhs,
+    Bounds,
+}
+
+/// A row read from the `ROWS` section, either the objective or a constraint.
+#[derive(Debug, Clone, Copy)]
+enum Row {
+    Objective,
+    Constraint(usize),
+    /// A free row besides the objective, which is ignored.
+    Free,
+}
+
+/// Reads a model in the free MPS format into a `ModelBuilder`.
+#[derive(Debug, Default)]
+pub struct MpsParser {
+    model: ModelBuilder,
+    maximize: bool,
+    rows: HashMap<String, Row>,
+    constraints: Vec<(String, ConstraintSense, LinearExpression, f64)>,
+    objective: LinearExpression,
+    objective_row: Option<String>,
+    integer: bool,
+    /// The variables whose lower bound was set by the `BOUNDS` section.
+    lower_bounded: Vec<bool>,
+}
+
+impl MpsParser {
+    /// Parses the text of a model in the free MPS format.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The model in MPS format.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ModelBuilder)` - The model described by the text.
+    /// * `Err(ModelParseError)` - The position and cause of the first error of the text.
+    pub fn parse(text: &str) -> Result<ModelBuilder, ModelParseError> {
+        let mut parser = MpsParser::default();
+        let mut section = None;
+
+        for (line_index, line) in text.lines().enumerate() {
+            if line.starts_with('*') || line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<(usize, &str)> = split_fields(line);
+            let line_number = line_index + 1;
+            let error = |index: usize, cause: String| {
+                let column = fields
+                    .get(index)
+                    .map_or(line.chars().count() + 1, |field| field.0);
+                ModelParseError::new(line_number, column, cause)
+            };
+
+            if !line.starts_with(char::is_whitespace) {
+                section = match fields[0].1.to_uppercase().as_str() {
+                    "NAME" => Some(Section::Name),
+                    "OBJSENSE" => Some(Section::ObjectiveSense),
+                    "ROWS" => Some(Section::Rows),
+                    "COLUMNS" => Some(Section::Columns),
+                    "RHS" => Some(Section::Rhs),
+                    "BOUNDS" => Some(Section::Bounds),
+                    "ENDATA" => return Ok(parser.finish()),
+                    "RANGES" => {
+                        return Err(error(0, "the RANGES section is not supported".to_string()))
+                    }
+                    _ => return Err(error(0, format!("unknown section '{}'", fields[0].1))),
+                };
+                // The objective sense may also follow its header on the same line, as `OBJSENSE MAX`
+                if section == Some(Section::ObjectiveSense) && fields.len() > 1 {
+                    parser
+                        .parse_objective_sense(&fields[1..])
+                        .map_err(|(index, cause)| error(index + 1, cause))?;
+                }
+                continue;
+            }
+
+            let result = match section {
+                Some(Section::Name) => Ok(()),
+                Some(Section::ObjectiveSense) => parser.parse_objective_sense(&fields),
+                Some(Section::Rows) => parser.parse_row(&fields),
+                Some(Section::Columns) => parser.parse_column(&fields),
+                Some(Section::Rhs) => parser.parse_rhs(&fields),
+                Some(Section::Bounds) => parser.parse_bound(&fields),
+                None => Err((0, "expected a section header".to_string())),
+            };
+            result.map_err(|(index, cause)| error(index, cause))?;
+        }
+
+        Ok(parser.finish())
+    }
+
+    fn parse_objective_sense(&mut self, fields: &[(usize, &str)]) -> Result<(), (usize, String)> {
+        match fields[0].1.to_uppercase().as_str() {
+            "MAX" | "MAXIMIZE" => self.maximize = true,
+            "MIN" | "MINIMIZE" => self.maximize = false,
+            sense => return Err((0, format!("unknown objective sense '{}'", sense))),
+        }
+        Ok(())
+    }
+
+    fn parse_row(&mut self, fields: &[(usize, &str)]) -> Result<(), (usize, String)> {
+        let [(_, sense), (_, name)] = fields else {
+            return Err((0, "expected a row type and a row name".to_string()));
+        };
+        let row = match sense.to_uppercase().as_str() {
+            "N" if self.objective_row.is_none() => {
+                self.objective_row = Some(name.to_string());
+                Row::Objective
+            }
+            "N" => Row::Free,
+            sense => {
+                let sense = match sense {
+                    "E" => ConstraintSense::Equal,
+                    "L" => ConstraintSense::LessOrEqual,
+                    "G" => ConstraintSense::GreaterOrEqual,
+                    _ => return Err((0, format!("unknown row type '{}'", sense))),
+                };
+                self.constraints
+                    .push((name.to_string(), sense, LinearExpression::new(), 0.0));
+                Row::Constraint(self.constraints.len() - 1)
+            }
+        };
+        if self.rows.insert(name.to_string(), row).is_some() {
+            return Err((1, format!("the row '{}' is declared twice", name)));
+        }
+        Ok(())
+    }
+
+    fn parse_column(&mut self, fields: &[(usize, &str)]) -> Result<(), (usize, String)> {
+        if fields.len() == 3 && fields[1].1 == "'MARKER'" {
+            match fields[2].1 {
+                "'INTORG'" => self.integer = true,
+                "'INTEND'" => self.integer = false,
+                marker => return Err((2, format!("unknown marker {}", marker))),
+            }
+            return Ok(());
+        }
+        if fields.len() != 3 && fields.len() != 5 {
+            return Err((
+                0,
+                "expected a column name and one or two row values".to_string(),
+            ));
+        }
+
+        let kind = if self.integer {
+            VariableKind::Integer
+        } else {
+            VariableKind::Continuous
+        };
+        let variable = self.model.add_variable(fields[0].1, kind);
+        self.lower_bounded
+            .resize(self.model.variable_count(), false);
+        for index in (1..fields.len()).step_by(2) {
+            let value = parse_number(fields[index + 1].1).map_err(|cause| (index + 1, cause))?;
+            match self.row(fields[index].1).map_err(|cause| (index, cause))? {
+                Row::Objective => self.objective.add_term(value, variable),
+                Row::Constraint(row) => self.constraints[row].2.add_term(value, variable),
+                Row::Free => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_rhs(&mut self, fields: &[(usize, &str)]) -> Result<(), (usize, String)> {
+        // The name of the right-hand side vector is optional
+        let first = match fields.len() {
+            2 | 4 => 0,
+            3 | 5 => 1,
+            _ => return Err((0, "expected one or two row values".to_string())),
+        };
+        for index in (first..fields.len()).step_by(2) {
+            let value = parse_number(fields[index + 1].1).map_err(|cause| (index + 1, cause))?;
+            match self.row(fields[index].1).map_err(|cause| (index, cause))? {
+                Row::Objective => self.objective.add_constant(-value),
+                Row::Constraint(row) => self.constraints[row].3 = value,
+                Row::Free => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_bound(&mut self, fields: &[(usize, &str)]) -> Result<(), (usize, String)> {
+        let bound_type = fields[0].1.to_uppercase();
+        let has_value = !matches!(bound_type.as_str(), "FR" | "MI" | "PL" | "BV");
+        // The name of the bound vector is optional
+        let column = match (has_value, fields.len()) {
+            (true, 3) | (false, 2) => 1,
+            (true, 4) | (false, 3) | (false, 4) => 2,
+            _ => return Err((0, "expected a bound type, a column and a value".to_string())),
+        };
+        let variable = self
+            .model
+            .variable(fields[column].1)
+            .ok_or_else(|| (column, format!("unknown column '{}'", fields[column].1)))?;
+        let value = if has_value {
+            parse_number(fields[column + 1].1).map_err(|cause| (column + 1, cause))?
+        } else {
+            0.0
+        };
+
+        let (mut lower, mut upper) = self.model.variable_bounds(variable);
+        match bound_type.as_str() {
+            "LO" | "LI" => {
+                lower = value;
+                self.lower_bounded[variable.index()] = true;
+            }
+            "UP" | "UI" => {
+                upper = value;
+                // A negative upper bound on a variable without lower bound makes it unbounded below
+                if value < 0.0 && lower == 0.0 && !self.lower_bounded[variable.index()] {
+                    lower = f64::NEG_INFINITY;
+                }
+            }
+            "FX" => (lower, upper) = (value, value),
+            "FR" => (lower, upper) = (f64::NEG_INFINITY, f64::INFINITY),
+            "MI" => lower = f64::NEG_INFINITY,
+            "PL" => upper = f64::INFINITY,
+            "BV" => {
+                self.model.set_kind(variable, VariableKind::Binary);
+                (lower, upper) = (lower.max(0.0), upper.min(1.0));
+            }
+            _ => return Err((0, format!("unsupported bound type '{}'", fields[0].1))),
+        }
+        if matches!(bound_type.as_str(), "LI" | "UI") {
+            self.model.set_kind(variable, VariableKind::Integer);
+        }
+        self.model.set_bounds(variable, lower, upper);
+        Ok(())
+    }
+
+    fn row(&self, name: &str) -> Result<Row, String> {
+        self.rows
+            .get(name)
+            .copied()
+            .ok_or_else(|| format!("unknown row '{}'", name))
+    }
+
+    /// Adds the objective and the constraints read to the model.
+    fn finish(mut self) -> ModelBuilder {
+        if self.maximize {
+            self.model.maximize(self.objective);
+        } else {
+            self.model.minimize(self.objective);
+        }
+        for (name, sense, expression, rhs) in self.constraints {
+            self.model
+                .add_constraint(LinearConstraint::new(expression, sense, rhs).with_name(name));
+        }
+        self.model
+    }
+}
+
+/// Splits a line into its whitespace separated fields, with their 1-based column.
+fn split_fields(line: &str) -> Vec<(usize, &str)> {
+    let mut fields = Vec::new();
+    let mut start = None;
+    for (column, (index, c)) in line.char_indices().enumerate() {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some((column + 1, index)),
+            (Some((field_column, field_start)), true) => {
+                fields.push((field_column, &line[field_start..index]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((field_column, field_start)) = start {
+        fields.push((field_column, &line[field_start..]));
+    }
+    fields
+}
+
+fn parse_number(field: &str) -> Result<f64, String> {
+    match field.to_lowercase().as_str() {
+        "inf" | "infinity" | "+inf" | "+infinity" => Ok(f64::INFINITY),
+        "-inf" | "-infinity" => Ok(f64::NEG_INFINITY),
+        _ => field
+            .parse::<f64>()
+            .map_err(|_| format!("invalid number '{}'", field)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_mps, MpsParser};
+    use crate::utils::{LinearExpression, ModelBuilder, ObjectiveType, VariableKind};
+
+    #[test]
+    fn test_parse_mps() {
+        let text = "* A comment line\n\
+                    NAME          EXAMPLE\n\
+                    OBJSENSE\n    MAX\n\
+                    ROWS\n N  profit\n L  lim1\n G  lim2\n E  myeqn\n\
+                    COLUMNS\n\
+                    \x20   MARKER  'MARKER'  'INTORG'\n\
+                    \x20   x  profit  1  lim1  1\n\
+                    \x20   x  lim2  1\n\
+                    \x20   MARKER  'MARKER'  'INTEND'\n\
+                    \x20   y  profit  2  lim1  1\n\
+                    \x20   y  myeqn  -1\n\
+                    \x20   z  profit  -1  myeqn  1\n\
+                    RHS\n    RHS  lim1  4  lim2  1\n    RHS  myeqn  7  profit  -3\n\
+                    BOUNDS\n UP BND  x  4\n MI BND  y\n UP BND  y  1\n BV BND  z\n\
+                    ENDATA\n";
+        let model = MpsParser::parse(text).unwrap();
+        assert_eq!(model.objective_type(), ObjectiveType::Maximize);
+        assert_eq!(model.objective().constant(), 3.0);
+        assert_eq!(model.objective().terms().len(), 3);
+        assert_eq!(model.constraints().len(), 3);
+        assert_eq!(model.constraints()[2].name(), "myeqn");
+        assert_eq!(model.constraints()[2].rhs(), 7.0);
+
+        let x = model.variable("x").unwrap();
+        assert_eq!(model.variable_kind(x), VariableKind::Integer);
+        assert_eq!(model.variable_bounds(x), (0.0, 4.0));
+        let y = model.variable("y").unwrap();
+        assert_eq!(model.variable_bounds(y), (f64::NEG_INFINITY, 1.0));
+        let z = model.variable("z").unwrap();
+        assert_eq!(model.variable_kind(z), VariableKind::Binary);
+
+        let text = write_mps(&model);
+        assert_eq!(write_mps(&MpsParser::parse(&text).unwrap()), text);
+    }
+
+    #[test]
+    fn test_parse_mps_errors() {
+        let error =
+            MpsParser::parse("ROWS\n N  obj\nCOLUMNS\n    x  other  1\nENDATA").unwrap_err();
+        assert_eq!((error.line(), error.column()), (4, 8));
+
+        let error =
+            MpsParser::parse("ROWS\n N  obj\nCOLUMNS\n    x  obj  one\nENDATA").unwrap_err();
+        assert_eq!((error.line(), error.column()), (4, 13));
+
+        assert!(MpsParser::parse("ROWS\n N  obj\nRANGES\nENDATA").is_err());
+        assert!(MpsParser::parse("ROWS\n X  obj\nENDATA").is_err());
+    }
+
+    #[test]
+    fn test_write_mps_round_trip_fixed_binary() {
+        let mut model = ModelBuilder::new();
+        let v = model.add_binary("v");
+        model.set_bounds(v, 1.0, 1.0);
+        model.maximize(LinearExpression::sum([v]));
+
+        let text = write_mps(&model);
+        assert!(text.contains(" BV BND  v\n FX BND  v  1\n"));
+
+        let read = MpsParser::parse(&text).unwrap();
+        let v = read.variable("v").unwrap();
+        assert_eq!(read.variable_kind(v), VariableKind::Binary);
+        assert_eq!(read.variable_bounds(v), (1.0, 1.0));
+    }
+}
@@ -1,240 +1,683 @@
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
-pub enum ObjectiveType {
-    #[default]
-    Minimize,
-    Maximize,
+//! This module implements a reader for models written in the CPLEX LP format.
+//!
+//! The supported sections are the objective (`Minimize`, `Maximize` and their aliases), the constraints
+//! (`Subject To`, `such that`, `st`, `s.t.`), `Bounds`, `General`/`Integer` and `Binary`, up to `End`.
+//! Expressions may span several lines, numbers may use the scientific notation and `\` starts a comment.
+//! Variables are declared on their first appearance, as continuous variables unless a section says otherwise.
+
+use super::builder::{
+    ConstraintSense, LinearConstraint, LinearExpression, ModelBuilder, VariableKind,
+};
+
+/// An error found while reading a model, at a 1-based line and column of the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelParseError {
+    line: usize,
+    column: usize,
+    cause: String,
 }
 
-#[derive(Debug)]
-pub enum BoundType {
-    Equality,
-    LessThanOrEqual,
-    GreaterThanOrEqual,
+impl ModelParseError {
+    pub(super) fn new(line: usize, column: usize, cause: impl Into<String>) -> Self {
+        ModelParseError {
+            line,
+            column,
+            cause: cause.into(),
+        }
+    }
+
+    /// Returns the line of the text where the error was found, starting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column of the text where the error was found, starting from 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the description of the error.
+    pub fn cause(&self) -> &str {
+        &self.cause
+    }
 }
 
-#[derive(Debug)]
-pub struct Bound {
-    _name: String,
-    pub lhs: (Vec<(f64, String)>, f64), // Coefficient and variable pairs for the left-hand side
-    pub rhs: (Vec<(f64, String)>, f64), // Right-hand side constant
-    pub bound_type: BoundType,
+impl std::fmt::Display for ModelParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.cause
+        )
+    }
+}
+
+impl std::error::Error for ModelParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Name(String),
+    Number(f64),
+    Plus,
+    Minus,
+    Colon,
+    Sense(ConstraintSense),
 }
 
-#[derive(Debug, Default, Clone)]
-pub struct Objective {
-    pub objective_type: ObjectiveType,
-    pub terms: (Vec<(f64, String)>, f64), // Coefficient and variable pairs
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+    column: usize,
+    /// Whether the token is the first one of its line, which is where section keywords are recognized.
+    line_start: bool,
 }
 
-#[derive(Debug)]
-pub struct ModelParser {
-    pub objective: Objective,
-    pub bounds: Vec<Bound>,
-    pub binary_variables: Vec<String>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Minimize,
+    Maximize,
+    Constraints,
+    Bounds,
+    General,
+    Binary,
+    End,
 }
 
-impl ModelParser {
-    pub fn new() -> Self {
-        ModelParser {
-            objective: Objective::default(),
-            bounds: Vec::new(),
-            binary_variables: Vec::new(),
-        }
-    }
+/// The characters, besides letters, allowed in the names of variables and constraints.
+const NAME_SYMBOLS: &str = "!\"#$%&()/,;?@_`'{}|~";
 
-    // Parses the text input into the ModelParser structure
-    pub fn parse(&mut self, text: &str) {
-        let mut lines = text.lines();
-        let mut mode = "";
-        let mut current_objective_terms = Vec::new();
-        let mut constant = 0.0;
+fn is_name_start(c: char) -> bool {
+    c.is_alphabetic() || NAME_SYMBOLS.contains(c)
+}
 
-        while let Some(line) = lines.next() {
-            let line = line.trim();
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '.' || NAME_SYMBOLS.contains(c)
+}
 
-            if line.is_empty() {
+/// Splits the text into tokens, skipping whitespace and comments.
+fn tokenize(text: &str) -> Result<Vec<Token>, ModelParseError> {
+    let mut tokens = Vec::new();
+    for (line_index, line) in text.lines().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let mut index = 0;
+        let mut line_start = true;
+        while index < chars.len() {
+            let c = chars[index];
+            let (line, column) = (line_index + 1, index + 1);
+            if c.is_whitespace() {
+                index += 1;
                 continue;
-            } else if line.starts_with("Minimize") || line.starts_with("Maximize") {
-                mode = "objective";
-                let objective_type = if line.starts_with("Minimize") {
-                    ObjectiveType::Minimize
-                } else {
-                    ObjectiveType::Maximize
-                };
-                self.objective = Objective {
-                    objective_type,
-                    terms: (Vec::new(), 0.0),
-                };
-            } else if line.starts_with("Subject To") {
-                mode = "constraints";
-                // Store any objective terms collected so far
-                self.objective.terms = (current_objective_terms.clone(), constant);
-            } else if line.starts_with("Binary") {
-                mode = "binary";
-            } else if line.starts_with("End") {
+            }
+            if c == '\\' {
                 break;
+            }
+
+            let next = chars.get(index + 1).copied();
+            let kind = if c.is_ascii_digit()
+                || (c == '.' && next.is_some_and(|c| c.is_ascii_digit()))
+            {
+                let start = index;
+                while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.')
+                {
+                    index += 1;
+                }
+                // The exponent is only read when digits follow, so that `2e` followed by a name stays a term
+                if index < chars.len() && (chars[index] == 'e' || chars[index] == 'E') {
+                    let mut exponent_end = index + 1;
+                    if exponent_end < chars.len()
+                        && (chars[exponent_end] == '+' || chars[exponent_end] == '-')
+                    {
+                        exponent_end += 1;
+                    }
+                    if exponent_end < chars.len() && chars[exponent_end].is_ascii_digit() {
+                        index = exponent_end;
+                        while index < chars.len() && chars[index].is_ascii_digit() {
+                            index += 1;
+                        }
+                    }
+                }
+                let literal: String = chars[start..index].iter().collect();
+                let number = literal.parse::<f64>().map_err(|_| {
+                    ModelParseError::new(line, column, format!("invalid number '{}'", literal))
+                })?;
+                TokenKind::Number(number)
+            } else if is_name_start(c) {
+                let start = index;
+                while index < chars.len() && is_name_char(chars[index]) {
+                    index += 1;
+                }
+                TokenKind::Name(chars[start..index].iter().collect())
             } else {
-                match mode {
-                    "objective" => {
-                        // Collects terms for the objective function across multiple lines
-                        let obj_line = line
-                            .split_whitespace()
-                            .skip(1)
-                            .collect::<Vec<&str>>()
-                            .join(" ");
-                        let expr = self.parse_expression(&obj_line);
-                        current_objective_terms.extend(expr.0);
-                        constant = expr.1;
+                index += 1;
+                match (c, next) {
+                    ('+', _) => TokenKind::Plus,
+                    ('-', _) => TokenKind::Minus,
+                    (':', _) => TokenKind::Colon,
+                    ('<', Some('=')) | ('=', Some('<')) => {
+                        index += 1;
+                        TokenKind::Sense(ConstraintSense::LessOrEqual)
                     }
-                    "constraints" => {
-                        // Parses each constraint line and adds it to the constraints vector
-                        self.bounds.push(self.parse_constraint(line));
+                    ('>', Some('=')) | ('=', Some('>')) => {
+                        index += 1;
+                        TokenKind::Sense(ConstraintSense::GreaterOrEqual)
                     }
-                    "binary" => {
-                        // Adds each binary variable name to the binary_variables vector
-                        self.binary_variables.push(line.to_string());
+                    ('<', _) => TokenKind::Sense(ConstraintSense::LessOrEqual),
+                    ('>', _) => TokenKind::Sense(ConstraintSense::GreaterOrEqual),
+                    ('=', _) => TokenKind::Sense(ConstraintSense::Equal),
+                    _ => {
+                        return Err(ModelParseError::new(
+                            line,
+                            column,
+                            format!("unexpected character '{}'", c),
+                        ))
                     }
-                    _ => {}
                 }
+            };
+            tokens.push(Token {
+                kind,
+                line,
+                column,
+                line_start,
+            });
+            line_start = false;
+        }
+    }
+    Ok(tokens)
+}
+
+/// The terms of an expression by variable name, and its constant.
+type ParsedExpression = (Vec<(f64, String)>, f64);
+
+/// Reads a model in the CPLEX LP format into a `ModelBuilder`.
+#[derive(Debug)]
+pub struct ModelParser {
+    tokens: Vec<Token>,
+    position: usize,
+    /// The position reported for errors found after the last token.
+    end: (usize, usize),
+    model: ModelBuilder,
+}
+
+impl ModelParser {
+    /// Parses the text of a model in the CPLEX LP format.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The model in LP format.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ModelBuilder)` - The model described by the text.
+    /// * `Err(ModelParseError)` - The position and cause of the first error of the text.
+    pub fn parse(text: &str) -> Result<ModelBuilder, ModelParseError> {
+        let end = (
+            text.lines().count().max(1),
+            text.lines().last().map_or(0, |line| line.chars().count()) + 1,
+        );
+        let mut parser = ModelParser {
+            tokens: tokenize(text)?,
+            position: 0,
+            end,
+            model: ModelBuilder::new(),
+        };
+        parser.parse_sections()?;
+        Ok(parser.model)
+    }
+
+    fn parse_sections(&mut self) -> Result<(), ModelParseError> {
+        match self.section_at(self.position)? {
+            Some((Section::Minimize, _)) | Some((Section::Maximize, _)) => {}
+            _ => return Err(self.error("expected the objective sense, Minimize or Maximize")),
+        }
+
+        while let Some((section, length)) = self.section_at(self.position)? {
+            self.position += length;
+            match section {
+                Section::Minimize | Section::Maximize => {
+                    let objective = self.parse_objective()?;
+                    if section == Section::Minimize {
+                        self.model.minimize(objective);
+                    } else {
+                        self.model.maximize(objective);
+                    }
+                }
+                Section::Constraints => self.parse_constraints()?,
+                Section::Bounds => self.parse_bounds()?,
+                Section::General => self.parse_declarations(VariableKind::Integer)?,
+                Section::Binary => self.parse_declarations(VariableKind::Binary)?,
+                Section::End => return Ok(()),
             }
         }
 
-        // Ensure any remaining terms for the objective are stored if they were parsed
-        self.objective.terms = (current_objective_terms, constant);
+        if self.position < self.tokens.len() {
+            return Err(self.error("expected a section keyword"));
+        }
+        Ok(())
+    }
+
+    /// Recognizes the section keyword starting at the given token, returning the section and its number of tokens.
+    fn section_at(&self, position: usize) -> Result<Option<(Section, usize)>, ModelParseError> {
+        let Some(token) = self.tokens.get(position) else {
+            return Ok(None);
+        };
+        let TokenKind::Name(name) = &token.kind else {
+            return Ok(None);
+        };
+        // A keyword opens a line and is not the label of a row
+        let next = self.tokens.get(position + 1);
+        if !token.line_start || next.is_some_and(|next| next.kind == TokenKind::Colon) {
+            return Ok(None);
+        }
+        let next_word = next
+            .filter(|next| next.line == token.line)
+            .and_then(|next| match &next.kind {
+                TokenKind::Name(name) => Some(name.to_lowercase()),
+                _ => None,
+            });
+
+        let section = match name.to_lowercase().as_str() {
+            "minimize" | "minimise" | "minimum" | "min" => (Section::Minimize, 1),
+            "maximize" | "maximise" | "maximum" | "max" => (Section::Maximize, 1),
+            "subject" if next_word.as_deref() == Some("to") => (Section::Constraints, 2),
+            "such" if next_word.as_deref() == Some("that") => (Section::Constraints, 2),
+            "st" | "s.t." => (Section::Constraints, 1),
+            "bounds" | "bound" => (Section::Bounds, 1),
+            "general" | "generals" | "gen" | "integer" | "integers" => (Section::General, 1),
+            "binary" | "binaries" | "bin" => (Section::Binary, 1),
+            "end" => (Section::End, 1),
+            "semi-continuous" | "semi" | "semis" | "sos" => {
+                return Err(ModelParseError::new(
+                    token.line,
+                    token.column,
+                    format!("the '{}' section is not supported", name),
+                ))
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(section))
+    }
+
+    fn at_section(&self) -> Result<bool, ModelParseError> {
+        Ok(self.position >= self.tokens.len() || self.section_at(self.position)?.is_some())
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
     }
 
-    fn parse_constraint(&self, line: &str) -> Bound {
-        let name_split: Vec<&str> = line.split(':').collect();
-        let _name = name_split[0].trim().to_string();
-        let rest = name_split[1].trim();
+    fn error(&self, cause: impl Into<String>) -> ModelParseError {
+        let (line, column) = self
+            .peek()
+            .map_or(self.end, |token| (token.line, token.column));
+        ModelParseError::new(line, column, cause)
+    }
 
-        let (lhs, bound_type, rhs) = if rest.contains("<=") {
-            let sides: Vec<&str> = rest.split("<=").collect();
+    /// Consumes a `name:` label if one starts at the current token.
+    fn parse_label(&mut self) -> Option<String> {
+        match (
+            self.tokens.get(self.position),
+            self.tokens.get(self.position + 1),
+        ) {
             (
-                self.parse_expression(sides[0]),
-                BoundType::LessThanOrEqual,
-                self.parse_expression(sides[1]),
-            )
-        } else if rest.contains(">=") {
-            let sides: Vec<&str> = rest.split(">=").collect();
+                Some(Token {
+                    kind: TokenKind::Name(name),
+                    ..
+                }),
+                Some(Token {
+                    kind: TokenKind::Colon,
+                    ..
+                }),
+            ) => {
+                let name = name.clone();
+                self.position += 2;
+                Some(name)
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_objective(&mut self) -> Result<LinearExpression, ModelParseError> {
+        self.parse_label();
+        let (terms, constant) = self.parse_expression(|parser| parser.at_section())?;
+        let mut objective = self.expression(&terms);
+        objective.add_constant(constant);
+        Ok(objective)
+    }
+
+    fn parse_constraints(&mut self) -> Result<(), ModelParseError> {
+        while !self.at_section()? {
+            let label = self.parse_label();
+            let (lhs, lhs_constant) = self.parse_expression(|parser| {
+                Ok(parser.at_section()?
+                    || matches!(
+                        parser.peek(),
+                        Some(Token {
+                            kind: TokenKind::Sense(_),
+                            ..
+                        })
+                    ))
+            })?;
+            let sense = match self.peek() {
+                Some(Token {
+                    kind: TokenKind::Sense(sense),
+                    ..
+                }) => *sense,
+                _ => return Err(self.error("expected a comparison operator")),
+            };
+            let mut line = self.tokens[self.position].line;
+            self.position += 1;
+
+            // The right-hand side starts after the operator and only goes on while its terms share a line,
+            // the next line then starting another constraint
+            let (rhs, rhs_constant) = self.parse_expression(|parser| {
+                let token = parser.peek();
+                let ended = parser.at_section()?
+                    || token.is_some_and(|token| token.line != line)
+                    || parser.parse_label_ahead();
+                if let Some(token) = token {
+                    line = token.line;
+                }
+                Ok(ended)
+            })?;
+            if let Some(Token {
+                kind: TokenKind::Sense(_),
+                ..
+            }) = self.peek()
+            {
+                return Err(self.error("ranged constraints are not supported"));
+            }
+
+            let mut expression = self.expression(&lhs);
+            for (coefficient, name) in rhs {
+                let variable = self.model.add_variable(name, VariableKind::Continuous);
+                expression.add_term(-coefficient, variable);
+            }
+            let constraint = LinearConstraint::new(expression, sense, rhs_constant - lhs_constant);
+            self.model.add_constraint(match label {
+                Some(label) => constraint.with_name(label),
+                None => constraint,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns whether a `name:` label starts at the current token.
+    fn parse_label_ahead(&self) -> bool {
+        matches!(
             (
-                self.parse_expression(sides[0]),
-                BoundType::GreaterThanOrEqual,
-                self.parse_expression(sides[1]),
-            )
-        } else if rest.contains("=") {
-            let sides: Vec<&str> = rest.split('=').collect();
+                self.tokens.get(self.position),
+                self.tokens.get(self.position + 1)
+            ),
             (
-                self.parse_expression(sides[0]),
-                BoundType::Equality,
-                self.parse_expression(sides[1]),
+                Some(Token {
+                    kind: TokenKind::Name(_),
+                    ..
+                }),
+                Some(Token {
+                    kind: TokenKind::Colon,
+                    ..
+                })
             )
-        } else {
-            panic!("Unrecognized constraint format");
-        };
-
-        Bound {
-            _name,
-            lhs,
-            rhs,
-            bound_type,
-        }
+        )
     }
 
-    fn parse_expression(&self, expr: &str) -> (Vec<(f64, String)>, f64) {
+    /// Parses a sum of terms until `stop` holds before a term, requiring at least one term.
+    fn parse_expression(
+        &mut self,
+        mut stop: impl FnMut(&Self) -> Result<bool, ModelParseError>,
+    ) -> Result<ParsedExpression, ModelParseError> {
         let mut terms = Vec::new();
         let mut constant = 0.0;
-        let mut current_sign = 1.0;
-        let mut buffer = String::new();
-
-        for (i, c) in expr.chars().enumerate() {
-            match c {
-                ' ' => {
-                    if !buffer.is_empty() {
-                        self.parse_and_add_term(
-                            &mut buffer,
-                            &mut terms,
-                            &mut constant,
-                            current_sign,
-                        );
-                        buffer.clear();
+        let mut first = true;
+
+        loop {
+            // The first term of a right-hand side may be on the line following the operator
+            if !first && stop(self)? {
+                break;
+            }
+            if first && self.at_section()? {
+                return Err(self.error("expected an expression"));
+            }
+
+            let mut sign = 1.0;
+            let mut signed = false;
+            while let Some(token) = self.peek() {
+                match token.kind {
+                    TokenKind::Plus => {}
+                    TokenKind::Minus => sign = -sign,
+                    _ => break,
+                }
+                signed = true;
+                self.position += 1;
+            }
+            if !first && !signed {
+                return Err(self.error("expected '+' or '-' between two terms"));
+            }
+
+            match self.peek().map(|token| token.kind.clone()) {
+                Some(TokenKind::Number(number)) => {
+                    self.position += 1;
+                    match self.peek().map(|token| token.kind.clone()) {
+                        Some(TokenKind::Name(name))
+                            if !self.at_section()? && !self.parse_label_ahead() =>
+                        {
+                            self.position += 1;
+                            terms.push((sign * number, name));
+                        }
+                        _ => constant += sign * number,
                     }
                 }
-                '+' => {
-                    if !buffer.is_empty() {
-                        self.parse_and_add_term(
-                            &mut buffer,
-                            &mut terms,
-                            &mut constant,
-                            current_sign,
-                        );
-                        buffer.clear();
+                Some(TokenKind::Name(name)) if !self.at_section()? => {
+                    self.position += 1;
+                    terms.push((sign, name));
+                }
+                _ => return Err(self.error("expected a number or a variable")),
+            }
+            first = false;
+        }
+
+        Ok((terms, constant))
+    }
+
+    /// Builds the expression of the given terms, declaring their variables.
+    fn expression(&mut self, terms: &[(f64, String)]) -> LinearExpression {
+        let mut expression = LinearExpression::new();
+        for (coefficient, name) in terms {
+            let variable = self
+                .model
+                .add_variable(name.as_str(), VariableKind::Continuous);
+            expression.add_term(*coefficient, variable);
+        }
+        expression
+    }
+
+    fn parse_bounds(&mut self) -> Result<(), ModelParseError> {
+        while !self.at_section()? {
+            match self.peek().map(|token| token.kind.clone()) {
+                Some(TokenKind::Name(name)) if !is_infinity(&name) => {
+                    self.position += 1;
+                    let variable = self.model.add_variable(name, VariableKind::Continuous);
+                    let (mut lower, mut upper) = self.model.variable_bounds(variable);
+                    match self.peek().map(|token| token.kind.clone()) {
+                        Some(TokenKind::Name(keyword)) if keyword.eq_ignore_ascii_case("free") => {
+                            self.position += 1;
+                            (lower, upper) = (f64::NEG_INFINITY, f64::INFINITY);
+                        }
+                        Some(TokenKind::Sense(sense)) => {
+                            self.position += 1;
+                            let value = self.parse_bound_value()?;
+                            match sense {
+                                ConstraintSense::LessOrEqual => upper = value,
+                                ConstraintSense::GreaterOrEqual => lower = value,
+                                ConstraintSense::Equal => (lower, upper) = (value, value),
+                            }
+                        }
+                        _ => return Err(self.error("expected a comparison operator or 'free'")),
                     }
-                    current_sign = 1.0;
+                    self.model.set_bounds(variable, lower, upper);
                 }
-                '-' => {
-                    if !buffer.is_empty() {
-                        self.parse_and_add_term(
-                            &mut buffer,
-                            &mut terms,
-                            &mut constant,
-                            current_sign,
-                        );
-                        buffer.clear();
+                _ => {
+                    let value = self.parse_bound_value()?;
+                    let sense = match self.peek() {
+                        Some(Token {
+                            kind: TokenKind::Sense(sense),
+                            ..
+                        }) => *sense,
+                        _ => return Err(self.error("expected a comparison operator")),
+                    };
+                    self.position += 1;
+                    let name = match self.peek().map(|token| token.kind.clone()) {
+                        Some(TokenKind::Name(name)) if !is_infinity(&name) => name,
+                        _ => return Err(self.error("expected a variable")),
+                    };
+                    self.position += 1;
+                    let variable = self.model.add_variable(name, VariableKind::Continuous);
+                    let (mut lower, mut upper) = self.model.variable_bounds(variable);
+                    match sense {
+                        ConstraintSense::LessOrEqual => lower = value,
+                        ConstraintSense::GreaterOrEqual => upper = value,
+                        ConstraintSense::Equal => (lower, upper) = (value, value),
+                    }
+                    // A double bound `l <= x <= u` goes on with the second operator
+                    if let Some(Token {
+                        kind: TokenKind::Sense(second),
+                        ..
+                    }) = self.peek()
+                    {
+                        let second = *second;
+                        if second != sense || sense == ConstraintSense::Equal {
+                            return Err(self.error("both operators of a double bound must match"));
+                        }
+                        self.position += 1;
+                        let value = self.parse_bound_value()?;
+                        match sense {
+                            ConstraintSense::LessOrEqual => upper = value,
+                            _ => lower = value,
+                        }
                     }
-                    current_sign = -1.0;
+                    self.model.set_bounds(variable, lower, upper);
                 }
-                _ => buffer.push(c), // Accumulate characters into buffer
             }
+        }
+        Ok(())
+    }
 
-            // If it's the last character, parse the remaining buffer
-            if i == expr.len() - 1 && !buffer.is_empty() {
-                self.parse_and_add_term(&mut buffer, &mut terms, &mut constant, current_sign);
+    /// Parses the signed number or infinity of a bound.
+    fn parse_bound_value(&mut self) -> Result<f64, ModelParseError> {
+        let mut sign = 1.0;
+        while let Some(token) = self.peek() {
+            match token.kind {
+                TokenKind::Plus => {}
+                TokenKind::Minus => sign = -sign,
+                _ => break,
             }
+            self.position += 1;
+        }
+        match self.peek().map(|token| token.kind.clone()) {
+            Some(TokenKind::Number(number)) => {
+                self.position += 1;
+                Ok(sign * number)
+            }
+            Some(TokenKind::Name(name)) if is_infinity(&name) => {
+                self.position += 1;
+                Ok(sign * f64::INFINITY)
+            }
+            _ => Err(self.error("expected a number or infinity")),
         }
-
-        (terms, constant)
     }
 
-    // Helper function to parse buffer content as either a constant or variable term
-    fn parse_and_add_term(
-        &self,
-        buffer: &mut String,
-        terms: &mut Vec<(f64, String)>,
-        constant: &mut f64,
-        sign: f64,
-    ) {
-        // Check if buffer is empty (nothing to parse)
-        if buffer.is_empty() {
-            return;
+    fn parse_declarations(&mut self, kind: VariableKind) -> Result<(), ModelParseError> {
+        while !self.at_section()? {
+            match self.peek().map(|token| token.kind.clone()) {
+                Some(TokenKind::Name(name)) => {
+                    self.position += 1;
+                    let variable = self.model.add_variable(name, kind);
+                    self.model.set_kind(variable, kind);
+                    // A binary variable lies in [0, 1], within the bounds it may already have
+                    if kind == VariableKind::Binary {
+                        let (lower, upper) = self.model.variable_bounds(variable);
+                        self.model
+                            .set_bounds(variable, lower.max(0.0), upper.min(1.0));
+                    }
+                }
+                _ => return Err(self.error("expected a variable")),
+            }
         }
+        Ok(())
+    }
+}
 
-        // Case 1: If the buffer is purely numeric, treat it as a constant
-        if buffer.chars().all(|c| c.is_digit(10) || c == '.') {
-            *constant += sign * buffer.parse::<f64>().unwrap();
+fn is_infinity(name: &str) -> bool {
+    name.eq_ignore_ascii_case("inf") || name.eq_ignore_ascii_case("infinity")
+}
 
-        // Case 2: If buffer starts with a digit and contains letters, it's a variable with a coefficient
-        } else if buffer.chars().next().unwrap().is_digit(10) {
-            let mut coefficient_str = String::new();
+#[cfg(test)]
+mod tests {
+    use super::ModelParser;
+    use crate::utils::{ConstraintSense, ObjectiveType, VariableKind};
 
-            // Extract leading numeric characters as the coefficient until we reach a letter
-            while let Some(c) = buffer.chars().next() {
-                if c.is_alphabetic() {
-                    break;
-                }
-                coefficient_str.push(buffer.remove(0)); // Add to coefficient and remove from buffer
-            }
+    #[test]
+    fn test_parse_sections() {
+        let text = "\\ A comment line\n\
+                    Maximize\n obj: 2 x + 3.5e-1 y - 1\n\
+                    subject to\n c1: x + y\n   + z <= 4 \\ trailing comment\n -x + 2 z >= -1\n\
+                    Bounds\n -inf <= x <= 10\n y free\n 1 <= z\n\
+                    General\n x\n\
+                    Binary\n b\n\
+                    End";
+        let model = ModelParser::parse(text).unwrap();
+        assert_eq!(model.objective_type(), ObjectiveType::Maximize);
+        assert_eq!(model.objective().constant(), -1.0);
+        assert_eq!(model.objective().terms()[1].0, 0.35);
 
-            // Parse coefficient and add term
-            let coefficient = sign * coefficient_str.parse::<f64>().unwrap();
-            terms.push((coefficient, buffer.clone())); // Remainder of buffer is the variable name
+        let constraints = model.constraints();
+        assert_eq!(constraints.len(), 2);
+        assert_eq!(constraints[0].name(), "c1");
+        assert_eq!(constraints[0].expression().terms().len(), 3);
+        assert_eq!(constraints[1].sense(), ConstraintSense::GreaterOrEqual);
+        assert_eq!(constraints[1].rhs(), -1.0);
 
-        // Case 3: If buffer starts with a letter, treat it as a variable with implicit coefficient 1
-        } else {
-            terms.push((sign, buffer.clone()));
-        }
+        let x = model.variable("x").unwrap();
+        assert_eq!(model.variable_kind(x), VariableKind::Integer);
+        assert_eq!(model.variable_bounds(x), (f64::NEG_INFINITY, 10.0));
+        let y = model.variable("y").unwrap();
+        assert_eq!(model.variable_bounds(y), (f64::NEG_INFINITY, f64::INFINITY));
+        let z = model.variable("z").unwrap();
+        assert_eq!(model.variable_bounds(z), (1.0, f64::INFINITY));
+        assert_eq!(model.variable_kind(z), VariableKind::Continuous);
+        let b = model.variable("b").unwrap();
+        assert_eq!(model.variable_kind(b), VariableKind::Binary);
+    }
+
+    #[test]
+    fn test_parse_aliases_and_constants() {
+        let text = "min x1\nst\n x1 >= 1 - x2\n R2: 3x1 - x2 = 0\nbin x1 x2\nend";
+        let model = ModelParser::parse(text).unwrap();
+        assert_eq!(model.objective_type(), ObjectiveType::Minimize);
+        assert_eq!(model.constraints()[0].expression().terms().len(), 2);
+        assert_eq!(model.constraints()[0].rhs(), 1.0);
+        assert_eq!(model.constraints()[1].name(), "R2");
+        assert_eq!(model.constraints()[1].expression().terms()[0].0, 3.0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error =
+            ModelParser::parse("Minimize\n obj: x\nSubject To\n c0: x + y 1\nEnd").unwrap_err();
+        assert_eq!((error.line(), error.column()), (4, 12));
+
+        let error =
+            ModelParser::parse("Minimize\n obj: x\nSubject To\n c0: x + y\nEnd").unwrap_err();
+        assert_eq!((error.line(), error.column()), (5, 1));
+        assert_eq!(error.cause(), "expected a comparison operator");
+
+        let error = ModelParser::parse("Minimize\n obj: x * y\nEnd").unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 9));
 
-        // Clear buffer after parsing
-        buffer.clear();
+        assert!(ModelParser::parse("Subject To\n c0: x >= 1\nEnd").is_err());
+        assert!(
+            ModelParser::parse("Minimize\n obj: x\nSubject To\n c0: 0 <= x <= 1\nEnd").is_err()
+        );
     }
 }
@@ -1,46 +1,70 @@
 use std::collections::HashMap;
 
-use good_lp::{solvers::highs::HighsSolution, Expression, Solution, Variable, VariableDefinition};
+use super::builder::{LinearExpression, ModelBuilder, VariableId};
 
-#[derive(Debug)]
+/// The solution of an executed model, giving the value of its variables and of its objective.
+#[derive(Debug, Clone)]
 pub struct ResultTranslator {
-    objective: Expression,
-    _var_names: HashMap<Variable, String>,
-    _variable_map: HashMap<String, (VariableDefinition, Variable)>,
-    solution: HighsSolution,
+    names: Vec<String>,
+    values: Vec<f64>,
+    objective: f64,
 }
 
 impl ResultTranslator {
-    pub fn new(
-        objective: Expression,
-        _var_names: HashMap<Variable, String>,
-        _variable_map: HashMap<String, (VariableDefinition, Variable)>,
-        solution: HighsSolution,
-    ) -> Self {
+    /// Creates a new `ResultTranslator` from the values of the variables of a model.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The executed `ModelBuilder`.
+    /// * `values` - The value of each variable of the model, indexed by `VariableId::index`.
+    ///
+    /// # Returns
+    ///
+    /// The `ResultTranslator` of the solution, its objective evaluated with the values.
+    pub fn new(model: &ModelBuilder, values: Vec<f64>) -> Self {
+        let objective = evaluate(model.objective(), &values);
         ResultTranslator {
+            names: model
+                .variables()
+                .map(|variable| model.variable_name(variable).to_string())
+                .collect(),
+            values,
             objective,
-            _var_names,
-            _variable_map,
-            solution,
         }
     }
 
     pub fn _get_variables(&self) -> HashMap<String, f64> {
-        let mut map = HashMap::new();
-        self._variable_map
-            .values()
-            .map(|(_var_def, var)| {
-                let name = self._var_names.get(var).unwrap();
-                (name, var)
-            })
-            .for_each(|(name, var)| {
-                let value = self.solution.value(*var);
-                map.insert(name.clone(), value);
-            });
-        map
+        self.names
+            .iter()
+            .cloned()
+            .zip(self.values.iter().copied())
+            .collect()
+    }
+
+    /// Returns the value of a variable of the executed `ModelBuilder`.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable` - The `VariableId` of the variable in the model.
+    ///
+    /// # Returns
+    ///
+    /// The value of the variable in the solution, `None` if the model has no such variable.
+    pub fn value(&self, variable: VariableId) -> Option<f64> {
+        self.values.get(variable.index()).copied()
     }
 
     pub fn get_objective(&self) -> f64 {
-        self.solution.eval(self.objective.clone())
+        self.objective
     }
 }
+
+/// Evaluates the expression with the given values of the variables.
+fn evaluate(expression: &LinearExpression, values: &[f64]) -> f64 {
+    expression
+        .terms()
+        .iter()
+        .map(|(coefficient, variable)| coefficient * values[variable.index()])
+        .sum::<f64>()
+        + expression.constant()
+}
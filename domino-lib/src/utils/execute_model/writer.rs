@@ -0,0 +1,225 @@
+//! This module writes a `ModelBuilder` in the CPLEX LP format.
+//!
+//! Long expressions are split over several lines, as LP readers limit the length of a line.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use super::builder::{
+    ConstraintSense, LinearExpression, ModelBuilder, ObjectiveType, VariableKind,
+};
+
+/// The number of terms written on each line of an expression.
+const TERMS_PER_LINE: usize = 8;
+
+/// Writes a model in the CPLEX LP format.
+///
+/// Every variable is written: the ones absent from the objective and the constraints get an explicit
+/// bound, so that reading the text back declares them too.
+///
+/// # Arguments
+///
+/// * `model` - The `ModelBuilder` to write.
+///
+/// # Returns
+///
+/// The text of the model in LP format.
+pub fn write_lp(model: &ModelBuilder) -> String {
+    let mut text = String::new();
+    text.push_str(match model.objective_type() {
+        ObjectiveType::Minimize => "Minimize\n",
+        ObjectiveType::Maximize => "Maximize\n",
+    });
+    text.push_str(" obj: ");
+    write_expression(&mut text, model, model.objective(), true);
+    text.push('\n');
+
+    if !model.constraints().is_empty() {
+        text.push_str("Subject To\n");
+        for constraint in model.constraints() {
+            let _ = write!(text, " {}: ", constraint.name());
+            write_expression(&mut text, model, constraint.expression(), false);
+            let sense = match constraint.sense() {
+                ConstraintSense::Equal => "=",
+                ConstraintSense::LessOrEqual => "<=",
+                ConstraintSense::GreaterOrEqual => ">=",
+            };
+            let _ = writeln!(text, " {} {}", sense, constraint.rhs());
+        }
+    }
+
+    let used: HashSet<_> = model
+        .constraints()
+        .iter()
+        .map(|constraint| constraint.expression())
+        .chain(std::iter::once(model.objective()))
+        .flat_map(|expression| expression.terms().iter().map(|(_, variable)| *variable))
+        .collect();
+    let bounds: Vec<String> = model
+        .variables()
+        .filter_map(|variable| {
+            let name = model.variable_name(variable);
+            let (lower, upper) = model.variable_bounds(variable);
+            let default = match model.variable_kind(variable) {
+                VariableKind::Binary => (0.0, 1.0),
+                _ => (0.0, f64::INFINITY),
+            };
+            if (lower, upper) == default {
+                let declared = used.contains(&variable)
+                    || model.variable_kind(variable) != VariableKind::Continuous;
+                return (!declared).then(|| format!(" {} >= 0", name));
+            }
+            Some(if lower == upper {
+                format!(" {} = {}", name, lower)
+            } else if lower == f64::NEG_INFINITY && upper == f64::INFINITY {
+                format!(" {} free", name)
+            } else if upper == f64::INFINITY {
+                format!(" {} >= {}", name, lower)
+            } else {
+                format!(" {} <= {} <= {}", lower, name, upper)
+            })
+        })
+        .collect();
+    if !bounds.is_empty() {
+        text.push_str("Bounds\n");
+        for bound in bounds {
+            text.push_str(&bound);
+            text.push('\n');
+        }
+    }
+
+    for (kind, section) in [
+        (VariableKind::Integer, "General"),
+        (VariableKind::Binary, "Binary"),
+    ] {
+        let mut variables = model
+            .variables()
+            .filter(|variable| model.variable_kind(*variable) == kind)
+            .peekable();
+        if variables.peek().is_some() {
+            let _ = writeln!(text, "{}", section);
+            for variable in variables {
+                let _ = writeln!(text, " {}", model.variable_name(variable));
+            }
+        }
+    }
+
+    text.push_str("End\n");
+    text
+}
+
+/// Writes the terms of an expression, followed by its constant if `with_constant` is set.
+///
+/// An expression without any term is written as its constant, as the LP format has no empty expression.
+fn write_expression(
+    text: &mut String,
+    model: &ModelBuilder,
+    expression: &LinearExpression,
+    with_constant: bool,
+) {
+    let mut terms: Vec<(f64, &str)> = expression
+        .terms()
+        .iter()
+        .map(|(coefficient, variable)| (*coefficient, model.variable_name(*variable)))
+        .collect();
+    if with_constant && expression.constant() != 0.0 || terms.is_empty() {
+        let constant = if with_constant {
+            expression.constant()
+        } else {
+            0.0
+        };
+        terms.push((constant, ""));
+    }
+
+    for (index, (coefficient, name)) in terms.into_iter().enumerate() {
+        if index > 0 && index % TERMS_PER_LINE == 0 {
+            text.push_str("\n   ");
+        }
+        text.push_str(match (index, coefficient < 0.0) {
+            (0, false) => "",
+            (0, true) => "- ",
+            (_, false) => " + ",
+            (_, true) => " - ",
+        });
+        let magnitude = coefficient.abs();
+        if name.is_empty() {
+            let _ = write!(text, "{}", magnitude);
+        } else if magnitude == 1.0 {
+            text.push_str(name);
+        } else {
+            let _ = write!(text, "{} {}", magnitude, name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_lp;
+    use crate::utils::{
+        ConstraintSense, LinearConstraint, LinearExpression, ModelBuilder, VariableKind,
+    };
+
+    #[test]
+    fn test_write_lp_round_trip() {
+        let mut model = ModelBuilder::new();
+        let x = model.add_binary("x");
+        let y = model.add_variable("y", VariableKind::Integer);
+        let z = model.add_variable("z", VariableKind::Continuous);
+        model.add_variable("unused", VariableKind::Continuous);
+        model.set_bounds(y, 0.0, 5.0);
+        model.set_bounds(z, f64::NEG_INFINITY, f64::INFINITY);
+        model.maximize(LinearExpression::sum([x, y]).with_term(-0.5, z));
+        let long =
+            LinearExpression::sum((0..20).map(|index| model.add_binary(format!("b{}", index))));
+        model.add_constraint(LinearConstraint::new(
+            long,
+            ConstraintSense::LessOrEqual,
+            3.0,
+        ));
+        model.add_constraint(LinearConstraint::new(
+            LinearExpression::new().with_term(-1.0, x).with_term(2.0, z),
+            ConstraintSense::GreaterOrEqual,
+            -1.5,
+        ));
+
+        let text = write_lp(&model);
+        assert!(text.lines().all(|line| line.len() < 255));
+        assert!(text.contains(" obj: x + y - 0.5 z\n"));
+        assert!(text.contains(" c1: - x + 2 z >= -1.5\n"));
+        assert!(text.contains(" 0 <= y <= 5\n z free\n unused >= 0\n"));
+
+        let read = ModelBuilder::from_lp(&text).unwrap();
+        assert_eq!(read.variable_count(), model.variable_count());
+        assert_eq!(
+            read.variable_bounds(read.variable("y").unwrap()),
+            (0.0, 5.0)
+        );
+        assert_eq!(
+            read.variable_kind(read.variable("unused").unwrap()),
+            VariableKind::Continuous
+        );
+        assert_eq!(read.constraints()[0].expression().terms().len(), 20);
+        let normalized = write_lp(&read);
+        assert_eq!(
+            write_lp(&ModelBuilder::from_lp(&normalized).unwrap()),
+            normalized
+        );
+    }
+
+    #[test]
+    fn test_write_lp_round_trip_fixed_binary() {
+        let mut model = ModelBuilder::new();
+        let v = model.add_binary("v");
+        model.set_bounds(v, 0.0, 0.0);
+        model.maximize(LinearExpression::sum([v]));
+
+        let text = write_lp(&model);
+        assert!(text.contains("Bounds\n v = 0\n"));
+        assert!(text.contains("Binary\n v\n"));
+
+        let read = ModelBuilder::from_lp(&text).unwrap();
+        let v = read.variable("v").unwrap();
+        assert_eq!(read.variable_kind(v), VariableKind::Binary);
+        assert_eq!(read.variable_bounds(v), (0.0, 0.0));
+    }
+}
@@ -1,19 +1,44 @@
 use std::collections::HashSet;
 
-use crate::utils::{get_n, DominoError, Puzzle, Tile};
+use crate::utils::{DominoError, Puzzle, Tile, Topology};
 
+/// Returns the tiles of the tileset of the puzzle that are not placed in it.
+///
+/// Tiles are equal and hash the same in both orientations, so each missing tile appears once, in
+/// an unspecified orientation.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` whose placed tiles are left out.
+///
+/// # Returns
+///
+/// * `Ok(HashSet<Tile>)` - The missing tiles, each once.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length does not match any tileset.
 pub fn get_missing_tiles(puzzle: &Puzzle) -> Result<HashSet<Tile>, DominoError> {
-    let n = get_n(puzzle)?;
+    get_missing_tiles_with(puzzle, Topology::Closed)
+}
+
+/// Returns the tiles of the tileset of the given topology that are not placed in the puzzle.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` whose placed tiles are left out.
+/// * `topology` - The `Topology` defining the tileset of the puzzle.
+///
+/// # Returns
+///
+/// * `Ok(HashSet<Tile>)` - The missing tiles, in either orientation.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length does not match any tileset of the topology.
+pub fn get_missing_tiles_with(
+    puzzle: &Puzzle,
+    topology: Topology,
+) -> Result<HashSet<Tile>, DominoError> {
+    let n = topology.get_n(puzzle)? as i32;
     let tileset: HashSet<Tile> = (0..(n + 1))
         .map(|i| ((0)..(n + 1)).map(|j| Tile(i, j)).collect::<Vec<Tile>>())
         .flatten()
-        .filter(|tile| {
-            if n % 2 == 0 {
-                true
-            } else {
-                (tile.0 - tile.1).abs() as i32 != ((n as i32 + 1) / 2)
-            }
-        })
+        .filter(|tile| topology.contains(n as usize, (tile.0 as usize, tile.1 as usize)))
         .collect();
     let used_tiles: HashSet<Tile> = puzzle.0
         .iter()
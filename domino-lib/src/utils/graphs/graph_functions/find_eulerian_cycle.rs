@@ -1,5 +1,41 @@
+use rand::Rng;
+
 use crate::{Graph, Node};
 
-use super::hierholzer::hierholzer;
+use super::hierholzer::{first_path_node, hierholzer, hierholzer_from};
+
+pub fn find_eulerian_cycle(graph: &Graph, random: bool) -> Vec<Node> { hierholzer(graph, random, &mut rand::thread_rng()) }
+
+/// Finds an Eulerian cycle of the graph, drawing the random choices from `rng` when `random` is set.
+///
+/// # Arguments
+///
+/// * `graph` - A reference to the `Graph` to traverse.
+/// * `random` - If `true`, the starting node and the next edges are chosen at random.
+/// * `rng` - The random number generator, a seeded one makes the cycle reproducible.
+///
+/// # Returns
+///
+/// The nodes of the cycle, the first one being repeated at the end.
+pub fn find_eulerian_cycle_with_rng<R: Rng + ?Sized>(graph: &Graph, random: bool, rng: &mut R) -> Vec<Node> { hierholzer(graph, random, rng) }
+
+pub fn find_eulerian_path(graph: &Graph, random: bool) -> Vec<Node> { find_eulerian_path_with_rng(graph, random, &mut rand::thread_rng()) }
 
-pub fn find_eulerian_cycle<'a>(graph: &'a Graph, random: bool) -> Vec<Node> { hierholzer(graph,random) }
+/// Finds an Eulerian path of the graph, drawing the random choices from `rng` when `random` is set.
+///
+/// The path starts from a node of odd degree, when there is one, and ends on the other; on a graph whose
+/// nodes all have an even degree it is an Eulerian cycle.
+///
+/// # Arguments
+///
+/// * `graph` - A reference to the `Graph` to traverse.
+/// * `random` - If `true`, the starting node and the next edges are chosen at random.
+/// * `rng` - The random number generator, a seeded one makes the path reproducible.
+///
+/// # Returns
+///
+/// The nodes of the path, one more than its edges.
+pub fn find_eulerian_path_with_rng<R: Rng + ?Sized>(graph: &Graph, random: bool, rng: &mut R) -> Vec<Node> {
+    let start = first_path_node(graph)(random, rng);
+    hierholzer_from(graph, start, random, rng)
+}
@@ -1,14 +1,37 @@
-use rand::seq::SliceRandom;
+use rand::{seq::SliceRandom, Rng};
 
 use crate::{Graph, Node};
 
-pub(super) fn first_node<'a>(graph: &'a Graph) -> impl Fn(bool) -> Node + 'a {
-    move |random: bool| {
-        let mut seed = rand::thread_rng();
+pub(super) fn first_node<'a, R: Rng + ?Sized>(graph: &'a Graph) -> impl Fn(bool, &mut R) -> Node + 'a {
+    move |random: bool, rng: &mut R| {
         if random {
-            graph.nodes.choose(&mut seed).unwrap().clone()
+            *graph.nodes.choose(rng).unwrap()
         } else {
-            graph.nodes.first().unwrap().clone()
+            *graph.nodes.first().unwrap()
+        }
+    }
+}
+
+/// Returns a closure choosing the first node of an Eulerian path: a node of odd degree if there is one.
+///
+/// A loop adds two to the degree of its node, so only the arcs to other nodes count towards the parity.
+pub fn first_path_node<'a, R: Rng + ?Sized>(graph: &'a Graph) -> impl Fn(bool, &mut R) -> Node + 'a {
+    move |random: bool, rng: &mut R| {
+        let odd_nodes: Vec<Node> = graph
+            .nodes
+            .iter()
+            .copied()
+            .filter(|node| {
+                let arcs = graph.adjacency.get(node).map_or(0, |arcs| {
+                    arcs.iter().filter(|arc| arc.destination != *node).count()
+                });
+                arcs % 2 == 1
+            })
+            .collect();
+        match (odd_nodes.is_empty(), random) {
+            (true, _) => first_node(graph)(random, rng),
+            (false, true) => *odd_nodes.choose(rng).unwrap(),
+            (false, false) => odd_nodes[0],
         }
     }
 }
@@ -5,25 +5,47 @@
 
 use super::{first_node::first_node, next_node::NextNodeBuilder};
 use crate::utils::graphs::{Arc, Graph, Node};
+use rand::Rng;
 use std::collections::HashSet;
 
-/// Generates a function that finds an Eulerian cycle using Hierholzer's algorithm.
+/// Finds an Eulerian cycle using Hierholzer's algorithm.
 ///
-/// The function returned by `hierholzer(graph)` takes a boolean flag (`random`) to determine
-/// whether the next node selection is randomized. It constructs an Eulerian circuit using a
-/// stack-based depth-first traversal.
+/// The `random` flag determines whether the first node and the next edge selections are randomized.
+/// The circuit is constructed using a stack-based depth-first traversal.
 ///
 /// # Arguments
 ///
 /// * `graph` - A reference to the `Graph` structure representing the input graph.
+/// * `random` - Whether the first node and the next edges are chosen at random.
+/// * `rng` - The random number generator used when `random` is set.
 ///
 /// # Returns
 ///
-/// A closure `(bool) -> Vec<Node>` that computes an Eulerian cycle when called.
-pub fn hierholzer<'a>(graph: &'a Graph, random: bool) -> Vec<Node> {
+/// A `Vec<Node>` holding the nodes of the Eulerian cycle, in the order they are visited.
+pub fn hierholzer<R: Rng + ?Sized>(graph: &Graph, random: bool, rng: &mut R) -> Vec<Node> {
+  let start = first_node(graph)(random, rng);
+  hierholzer_from(graph, start, random, rng)
+}
+
+/// Runs Hierholzer's algorithm from the given node.
+///
+/// Started from a node of odd degree, the traversal ends on the other node of odd degree, so that the
+/// returned sequence is an Eulerian path rather than a cycle.
+///
+/// # Arguments
+///
+/// * `graph` - A reference to the `Graph` structure representing the input graph.
+/// * `start` - The node the traversal starts from.
+/// * `random` - Whether the next edges are chosen at random.
+/// * `rng` - The random number generator used when `random` is set.
+///
+/// # Returns
+///
+/// The nodes of the traversal, the first one being `start`.
+pub fn hierholzer_from<R: Rng + ?Sized>(graph: &Graph, start: Node, random: bool, rng: &mut R) -> Vec<Node> {
   let mut circuit: Vec<Node> = Vec::new();
   let mut visited: HashSet<Arc> = HashSet::new();
-  let mut stack: Vec<Node> = vec![first_node(graph)(random)];
+  let mut stack: Vec<Node> = vec![start];
 
   while let Some(current_vertex) = stack.pop() {
       // Choose the next node using NextNodeBuilder
@@ -31,7 +53,7 @@ pub fn hierholzer<'a>(graph: &'a Graph, random: bool) -> Vec<Node> {
           .with_random(random)
           .with_visited(visited.clone())
           .with_current_vertex(current_vertex.clone())
-          .build();
+          .build(rng);
 
       if let Some(unvisited_index) = unvisited_edge_index {
           stack.push(current_vertex.clone());
@@ -67,7 +89,11 @@ pub fn hierholzer<'a>(graph: &'a Graph, random: bool) -> Vec<Node> {
 #[cfg(test)]
 mod tests {
     use super::Graph;
-    use crate::utils::graphs::find_eulerian_cycle;
+    use crate::utils::{
+        graphs::{find_eulerian_cycle, find_eulerian_cycle_with_rng, find_eulerian_path},
+        Topology,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
 
     /// Tests the `find_eulerian_cycle` function for graphs of different sizes.
     ///
@@ -85,4 +111,27 @@ mod tests {
             assert_eq!(eulerian_cycle.len(), expected_len + 1);
         });
     }
+
+    /// Tests that the same seed always yields the same random Eulerian cycle.
+    #[test]
+    fn test_find_eulerian_cycle_seeded() {
+        let graph = Graph::regular(6);
+        let first = find_eulerian_cycle_with_rng(&graph, true, &mut StdRng::seed_from_u64(42));
+        let second = find_eulerian_cycle_with_rng(&graph, true, &mut StdRng::seed_from_u64(42));
+        assert_eq!(first, second);
+    }
+
+    /// Tests that the Eulerian path of an open tileset uses every tile and joins its two odd nodes.
+    #[test]
+    fn test_find_eulerian_path() {
+        (3..=6).for_each(|n| {
+            let graph = Graph::with_topology(n, Topology::Open);
+            let path = find_eulerian_path(&graph, true);
+            assert_eq!(path.len(), Topology::Open.puzzle_length(n) + 1);
+            if n % 2 == 1 {
+                let ends = (path[0].min(path[path.len() - 1]), path[0].max(path[path.len() - 1]));
+                assert_eq!(ends, (0, ((n + 1) / 2) as i32));
+            }
+        });
+    }
 }
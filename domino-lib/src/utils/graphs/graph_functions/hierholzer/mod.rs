@@ -2,4 +2,5 @@ mod first_node;
 mod hierholzer;
 mod next_node;
 
-pub use hierholzer::hierholzer;
+pub use first_node::first_path_node;
+pub use hierholzer::{hierholzer, hierholzer_from};
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use rand::seq::IteratorRandom;
+use rand::{seq::IteratorRandom, Rng};
 
 use crate::{Arc, Graph, Node};
 
@@ -38,13 +38,12 @@ impl<'a> NextNodeBuilder<'a> {
         self
     }
 
-    // Build method: computes and returns the next Node
-    pub fn build(self) -> Option<usize> {
+    // Build method: computes and returns the next Node, drawing from rng when random
+    pub fn build<R: Rng + ?Sized>(self, rng: &mut R) -> Option<usize> {
         let graph = self.graph;
         let random = self.random.unwrap_or(false);
         let visited = self.visited.unwrap_or(HashSet::new());
         let current_vertex = self.current_vertex.unwrap();
-        let mut seed = rand::thread_rng();
         let mut edges_iterator = graph.adjacency.get(&current_vertex.clone()).unwrap().iter();
         if random {
             edges_iterator
@@ -58,7 +57,7 @@ impl<'a> NextNodeBuilder<'a> {
                         current_vertex.clone(),
                     )))
                 })
-                .choose(&mut seed)
+                .choose(rng)
                 .map(|(index, _)| index)
         } else {
             let position = edges_iterator.position(|arc| {
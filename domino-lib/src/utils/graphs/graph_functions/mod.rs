@@ -1,4 +1,6 @@
 mod find_eulerian_cycle;
 mod hierholzer;
 
-pub use find_eulerian_cycle::find_eulerian_cycle;
+pub use find_eulerian_cycle::{
+    find_eulerian_cycle, find_eulerian_cycle_with_rng, find_eulerian_path, find_eulerian_path_with_rng,
+};
@@ -1,4 +1,5 @@
 use super::{Arc, Node};
+use crate::utils::Topology;
 use std::collections::HashMap;
 
 #[derive(Debug, Default, Clone)]
@@ -9,16 +10,27 @@ pub struct Graph {
 
 impl Graph {
     pub fn regular(n: usize) -> Self {
+        Graph::with_topology(n, Topology::Closed)
+    }
+
+    /// Creates the graph of the tileset of size `n` for the given topology, each tile being an edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The size of the tileset.
+    /// * `topology` - The `Topology` of the puzzles built on the tileset.
+    ///
+    /// # Returns
+    ///
+    /// The `Graph` whose nodes are the values `[0, n]`.
+    pub fn with_topology(n: usize, topology: Topology) -> Self {
         Graph {
             nodes: (0..(n + 1) as i32).collect(),
             adjacency: (0..(n + 1) as i32)
                 .map(|source_node| {
                     let edges = (0..(n + 1))
                         .filter_map(|destination_node| {
-                            if n % 2 == 0
-                                || (source_node as f32 - destination_node as f32).abs() as i32
-                                    != ((n as i32 + 1) / 2)
-                            {
+                            if topology.contains(n, (source_node as usize, destination_node)) {
                                 Some(Arc {
                                     source: source_node.into(),
                                     destination: (destination_node as i32).into(),
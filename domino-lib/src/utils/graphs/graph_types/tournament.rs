@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::Tile;
+use crate::{utils::Topology, Tile};
 
 use super::{Arc, Node};
 
@@ -24,6 +24,27 @@ impl Tournament {
     /// * `Ok(Tournament)` if the input forms a valid tournament graph.
     /// * `Err(String)` if the input does not satisfy the tournament properties.
     pub fn new(arcs: Vec<Tile>) -> Result<Self, String> {
+        Tournament::with_topology(arcs, Topology::Closed)
+    }
+
+    /// Creates a new tournament from the arcs of a chain of the given topology.
+    ///
+    /// The arcs of an open chain do not return to their first node: its first node has one more outgoing
+    /// arc, its last node one more incoming arc, and both of them one more arc than the other nodes.
+    ///
+    /// # Arguments
+    /// * `arcs` - A vector of tuples representing directed edges, in the order of the chain.
+    /// * `topology` - The `Topology` of the chain.
+    ///
+    /// # Returns
+    /// * `Ok(Tournament)` if the input forms a valid tournament graph.
+    /// * `Err(String)` if the input does not satisfy the tournament properties.
+    pub fn with_topology(arcs: Vec<Tile>, topology: Topology) -> Result<Self, String> {
+        // The nodes at the free ends of an open chain, when they differ
+        let ends: Option<(Node, Node)> = match (topology, arcs.first(), arcs.last()) {
+            (Topology::Open, Some(first), Some(last)) if first.0 != last.1 => Some((first.0, last.1)),
+            _ => None,
+        };
         let mut nodes: HashSet<Node> = HashSet::new();
         let mut adjacency: HashMap<Node, Vec<Arc>> = HashMap::new();
         let mut in_degrees: HashMap<Node, usize> = HashMap::new();
@@ -53,7 +74,13 @@ impl Tournament {
                 let in_deg = *in_degrees.get(&node).unwrap_or(&0);
                 let out_deg = *out_degrees.get(&node).unwrap_or(&0);
 
-                if in_deg != out_deg {
+                let (imbalance, extra_degree): (i64, usize) = match ends {
+                    Some((first, _)) if node == first => (1, 1),
+                    Some((_, last)) if node == last => (-1, 1),
+                    _ => (0, 0),
+                };
+
+                if out_deg as i64 - in_deg as i64 != imbalance {
                     let err_msg = format!(
                       "Invalid tournament: Node {} is unbalanced, has in-degree {} and out-degree {}",
                       node, in_deg, out_deg
@@ -61,7 +88,7 @@ impl Tournament {
                     return Err(err_msg);
                 }
 
-                let expected_degree = ((n as f32 / 2.0).floor() as usize + 1) * 2;
+                let expected_degree = ((n as f32 / 2.0).floor() as usize + 1) * 2 + extra_degree;
                 if in_deg + out_deg != expected_degree {
                     let err_msg = format!(
                     "Invalid tournament: Node {} does not have the right number of arcs for a tournament, found {} expected {}",
@@ -162,4 +189,22 @@ mod tests {
         println!("Result: {result:?}");
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_tournament_open_chain() {
+        // The tileset of size 3 with [0,2] kept, walked from 0 to 2
+        let arcs: Vec<Tile> = vec![
+            (0, 0).into(),
+            (0, 1).into(),
+            (1, 1).into(),
+            (1, 2).into(),
+            (2, 2).into(),
+            (2, 3).into(),
+            (3, 3).into(),
+            (3, 0).into(),
+            (0, 2).into(),
+        ];
+        assert!(Tournament::with_topology(arcs.clone(), Topology::Open).is_ok());
+        assert!(Tournament::new(arcs).is_err());
+    }
 }
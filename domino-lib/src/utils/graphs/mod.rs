@@ -2,6 +2,8 @@ mod get_missing_tiles;
 mod graph_functions;
 mod graph_types;
 
-pub use get_missing_tiles::get_missing_tiles;
-pub use graph_functions::find_eulerian_cycle;
+pub use get_missing_tiles::{get_missing_tiles, get_missing_tiles_with};
+pub use graph_functions::{
+    find_eulerian_cycle, find_eulerian_cycle_with_rng, find_eulerian_path, find_eulerian_path_with_rng,
+};
 pub use graph_types::{Arc, Graph, Node, Tournament};
@@ -1,11 +1,27 @@
+mod canonical;
 mod error;
 mod execute_model;
 mod get_n;
 mod graphs;
+mod tileset;
+mod topology;
 mod types;
 
+pub(crate) use canonical::canonical_key;
+pub use canonical::{CanonicalPuzzle, PuzzleEquivalence};
 pub use error::DominoError;
-pub use execute_model::{Model, ResultTranslator};
+#[cfg(feature = "highs")]
+pub use execute_model::HighsBackend;
+#[cfg(feature = "microlp")]
+pub use execute_model::MicroLpBackend;
+pub use execute_model::{
+    default_backend, ConstraintSense, LinearConstraint, LinearExpression, Model, ModelBuilder,
+    ModelParseError, ObjectiveType, ResultTranslator, SolverBackend, VariableId, VariableKind,
+};
 pub use get_n::get_n;
-pub use graphs::{find_eulerian_cycle, get_missing_tiles, Arc, Graph, Node, Tournament};
+pub use graphs::{
+    find_eulerian_cycle, find_eulerian_cycle_with_rng, find_eulerian_path, find_eulerian_path_with_rng,
+    get_missing_tiles, get_missing_tiles_with, Arc, Graph, Node, Tournament};
+pub use tileset::TileSet;
+pub use topology::Topology;
 pub use types::{Puzzle, Solution, Tile};
@@ -0,0 +1,189 @@
+//! This module defines the `TileSet`, the multiset of tiles a puzzle is made of.
+//!
+//! By default a puzzle of size `n` uses the complete double-`n` set, less the tiles its `Topology` leaves out
+//! for odd sizes. An explicit `TileSet` lets a puzzle use any collection of tiles instead, such as a house-rule
+//! set or a set holding several copies of some tiles.
+
+use std::collections::HashMap;
+
+use super::{DominoError, Puzzle, Tile, Topology};
+
+/// A multiset of tiles, each tile being kept in its `[low,high]` orientation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileSet {
+    tiles: Vec<Tile>,
+}
+
+impl TileSet {
+    /// Creates a new `TileSet` from the given tiles, in any order and orientation.
+    ///
+    /// # Arguments
+    ///
+    /// * `tiles` - The tiles of the set, a tile appearing once for each of its copies.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TileSet)` - The set of the given tiles.
+    /// * `Err(DominoError::InvalidTileSet)` - If the set is empty or a tile has a negative value.
+    pub fn new(tiles: Vec<Tile>) -> Result<TileSet, DominoError> {
+        if tiles.is_empty() {
+            return Err(DominoError::InvalidTileSet("the tileset is empty".to_string()));
+        }
+        if let Some(tile) = tiles.iter().find(|tile| tile.0 < 0 || tile.1 < 0) {
+            return Err(DominoError::InvalidTileSet(format!(
+                "{} has a negative value",
+                tile
+            )));
+        }
+
+        let mut tiles: Vec<Tile> = tiles
+            .into_iter()
+            .map(|tile| Tile(tile.0.min(tile.1), tile.0.max(tile.1)))
+            .collect();
+        tiles.sort_by_key(|tile| (tile.0, tile.1));
+        Ok(TileSet { tiles })
+    }
+
+    /// Returns the complete double-`n` set, less the tiles balancing the degrees for odd `n`.
+    pub fn regular(n: usize) -> TileSet {
+        TileSet::with_topology(n, Topology::Closed)
+    }
+
+    /// Returns the double-`n` set used by the puzzles of the given topology.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The largest value of the set.
+    /// * `topology` - The `Topology` of the puzzles, which decides the tiles left out for odd `n`.
+    ///
+    /// # Returns
+    ///
+    /// The `TileSet` holding each tile of the set once.
+    pub fn with_topology(n: usize, topology: Topology) -> TileSet {
+        let tiles = (0..=n)
+            .flat_map(|low| (low..=n).map(move |high| (low, high)))
+            .filter(|&tile| topology.contains(n, tile))
+            .map(|(low, high)| Tile(low as i32, high as i32))
+            .collect();
+        TileSet { tiles }
+    }
+
+    /// Returns the double-`n` set of the size retrieved from the length of the puzzle.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TileSet)` - The set of the size of the puzzle.
+    /// * `Err(DominoError::InvalidLength)` - If the length of the puzzle does not match any size of the topology.
+    pub(crate) fn for_puzzle(puzzle: &Puzzle, topology: Topology) -> Result<TileSet, DominoError> {
+        Ok(TileSet::with_topology(topology.get_n(puzzle)?, topology))
+    }
+
+    /// Returns the tiles of the set, sorted and in their `[low,high]` orientation.
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    /// Returns the number of tiles of the set, copies included.
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Returns whether the set has no tile.
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Returns the largest value of the tiles of the set.
+    pub fn n(&self) -> usize {
+        self.tiles.iter().map(|tile| tile.1).max().unwrap_or(0) as usize
+    }
+
+    /// Returns the number of copies of the tile in the set, in either orientation.
+    pub fn count(&self, tile: Tile) -> usize {
+        self.tiles.iter().filter(|candidate| **candidate == tile).count()
+    }
+
+    /// Returns the tiles of the set that are not placed in the puzzle.
+    ///
+    /// Each placed tile consumes one copy; a placed tile without copies left is not part of the set and is
+    /// ignored, so the puzzle then has fewer empty positions than missing tiles.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle` - A reference to the `Puzzle` whose placed tiles are left out.
+    ///
+    /// # Returns
+    ///
+    /// The missing tiles, sorted and in their `[low,high]` orientation.
+    pub fn missing_tiles(&self, puzzle: &Puzzle) -> Vec<Tile> {
+        let mut copies: HashMap<Tile, usize> = HashMap::new();
+        for tile in &self.tiles {
+            *copies.entry(*tile).or_default() += 1;
+        }
+        for tile in puzzle.0.iter().flatten() {
+            if let Some(count) = copies.get_mut(tile) {
+                *count = count.saturating_sub(1);
+            }
+        }
+
+        let mut missing = Vec::new();
+        for tile in &self.tiles {
+            let count = copies.get_mut(tile).expect("Every tile of the set is counted");
+            if *count > 0 {
+                *count -= 1;
+                missing.push(*tile);
+            }
+        }
+        missing
+    }
+
+    /// Returns whether the tiles of the solution are exactly the tiles of the set.
+    pub(crate) fn is_used_by(&self, solution: &[Tile]) -> bool {
+        let mut tiles: Vec<Tile> = solution
+            .iter()
+            .map(|tile| Tile(tile.0.min(tile.1), tile.0.max(tile.1)))
+            .collect();
+        tiles.sort_by_key(|tile| (tile.0, tile.1));
+        tiles == self.tiles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TileSet;
+    use crate::{DominoError, Puzzle, Tile, Topology};
+
+    #[test]
+    fn test_tileset_regular() {
+        for n in 0..8 {
+            assert_eq!(TileSet::regular(n).len(), Topology::Closed.puzzle_length(n));
+            assert_eq!(TileSet::regular(n).n(), n);
+        }
+        assert_eq!(TileSet::regular(3).count(Tile(2, 0)), 0);
+        assert_eq!(TileSet::with_topology(3, Topology::Open).count(Tile(2, 0)), 1);
+    }
+
+    #[test]
+    fn test_tileset_duplicates() {
+        let tileset = TileSet::new(vec![Tile(1, 0), Tile(0, 1), Tile(0, 0), Tile(1, 1)]).unwrap();
+        assert_eq!(tileset.count(Tile(0, 1)), 2);
+
+        let puzzle: Puzzle = vec![Some(Tile(0, 1)), None, Some(Tile(1, 1)), None].into();
+        assert_eq!(tileset.missing_tiles(&puzzle), vec![Tile(0, 0), Tile(0, 1)]);
+        assert!(tileset.is_used_by(&[Tile(0, 0), Tile(0, 1), Tile(1, 1), Tile(1, 0)]));
+        assert!(!tileset.is_used_by(&[Tile(0, 0), Tile(0, 1), Tile(1, 1), Tile(1, 1)]));
+    }
+
+    #[test]
+    fn test_tileset_invalid() {
+        assert!(matches!(TileSet::new(vec![]), Err(DominoError::InvalidTileSet(_))));
+        assert!(matches!(
+            TileSet::new(vec![Tile(-1, 0)]),
+            Err(DominoError::InvalidTileSet(_))
+        ));
+
+        // A given outside of the set does not consume any tile
+        let puzzle: Puzzle = vec![Some(Tile(4, 4)), None, None].into();
+        assert_eq!(TileSet::regular(2).missing_tiles(&puzzle).len(), 6);
+    }
+}
@@ -0,0 +1,120 @@
+//! This module defines the `Topology` of a puzzle: whether its tiles form a closed cycle or an open line.
+//!
+//! A closed puzzle links its last position back to the first one and uses a tileset whose nodes all have an
+//! even degree, so that it is walked by an Eulerian cycle. An open puzzle has two free ends: for odd sizes its
+//! tileset keeps the tile `[0,(n+1)/2]`, whose nodes then have an odd degree and are the ends of an Eulerian path.
+
+use super::{get_n, DominoError, Puzzle};
+
+/// Selects whether the tiles of a puzzle form a closed cycle or an open line with two free ends.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topology {
+    /// The last position is followed by the first one, the solution is an Eulerian cycle.
+    #[default]
+    Closed,
+    /// The first and last positions are free ends, the solution is an Eulerian path.
+    Open,
+}
+
+impl Topology {
+    /// Returns whether the tile belongs to the tileset of size `n`.
+    ///
+    /// For odd `n` the tiles whose values differ by `(n+1)/2` are left out, to balance the degrees of the
+    /// nodes; the open topology keeps one of them, `[0,(n+1)/2]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The size of the tileset.
+    /// * `tile` - The two values of the tile, in either orientation.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the tile is part of the tileset.
+    pub fn contains(self, n: usize, tile: (usize, usize)) -> bool {
+        let (low, high) = (tile.0.min(tile.1), tile.0.max(tile.1));
+        if high > n {
+            return false;
+        }
+        n.is_multiple_of(2)
+            || high - low != n.div_ceil(2)
+            || (self == Topology::Open && low == 0)
+    }
+
+    /// Returns the number of positions of a puzzle of size `n`.
+    pub fn puzzle_length(self, n: usize) -> usize {
+        match (self, n.is_multiple_of(2)) {
+            (_, true) => (n + 1) * (n + 2) / 2,
+            (Topology::Closed, false) => (n + 1) * (n + 1) / 2,
+            (Topology::Open, false) => (n + 1) * (n + 1) / 2 + 1,
+        }
+    }
+
+    /// Retrieves the size of the puzzle from its length.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle` - A reference to the `Puzzle` to measure.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The size `n` of the puzzle.
+    /// * `Err(DominoError::InvalidLength)` - If no puzzle of this topology has the length of `puzzle`.
+    pub fn get_n(self, puzzle: &Puzzle) -> Result<usize, DominoError> {
+        match self {
+            Topology::Closed => get_n(puzzle).map(|n| n as usize),
+            Topology::Open => (0..=puzzle.len())
+                .find(|&n| self.puzzle_length(n) >= puzzle.len())
+                .filter(|&n| puzzle.len() > 0 && self.puzzle_length(n) == puzzle.len())
+                .ok_or(DominoError::InvalidLength),
+        }
+    }
+
+    /// Returns the position following `position` in a puzzle of length `len`, `None` past the end of an open line.
+    pub fn next(self, position: usize, len: usize) -> Option<usize> {
+        match self {
+            Topology::Closed => Some((position + 1) % len),
+            Topology::Open => (position + 1 < len).then_some(position + 1),
+        }
+    }
+
+    /// Returns the position preceding `position` in a puzzle of length `len`, `None` before the start of an open line.
+    pub fn previous(self, position: usize, len: usize) -> Option<usize> {
+        match self {
+            Topology::Closed => Some((position + len - 1) % len),
+            Topology::Open => position.checked_sub(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Topology;
+    use crate::{DominoError, Puzzle};
+
+    #[test]
+    fn test_topology_lengths() {
+        for n in 0..10 {
+            for topology in [Topology::Closed, Topology::Open] {
+                let tiles = (0..=n)
+                    .flat_map(|low| (low..=n).map(move |high| (low, high)))
+                    .filter(|&tile| topology.contains(n, tile))
+                    .count();
+                assert_eq!(topology.puzzle_length(n), tiles);
+
+                let puzzle: Puzzle = vec![None; tiles].into();
+                assert_eq!(topology.get_n(&puzzle), Ok(n));
+            }
+        }
+        let puzzle: Puzzle = vec![None; 8].into();
+        assert_eq!(Topology::Open.get_n(&puzzle), Err(DominoError::InvalidLength));
+    }
+
+    #[test]
+    fn test_topology_neighbours() {
+        assert_eq!(Topology::Closed.next(7, 8), Some(0));
+        assert_eq!(Topology::Closed.previous(0, 8), Some(7));
+        assert_eq!(Topology::Open.next(7, 8), None);
+        assert_eq!(Topology::Open.previous(0, 8), None);
+        assert_eq!(Topology::Open.next(3, 8), Some(4));
+    }
+}
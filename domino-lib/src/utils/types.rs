@@ -3,7 +3,7 @@ use std::vec::IntoIter;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, Hash, Eq)]
+#[derive(Debug, Clone, Copy, Eq)]
 pub struct Tile(pub i32, pub i32);
 
 impl std::fmt::Display for Tile {
@@ -54,6 +54,14 @@ impl PartialEq for Tile {
     }
 }
 
+// Tiles are equal regardless of their orientation, so they must hash the same way
+impl std::hash::Hash for Tile {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.min(self.1).hash(state);
+        self.0.max(self.1).hash(state);
+    }
+}
+
 impl From<(i32, i32)> for Tile {
     fn from(value: (i32, i32)) -> Self {
         Tile(value.0, value.1)
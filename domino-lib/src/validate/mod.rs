@@ -1,17 +1,114 @@
-use model::{compute_model, variables::create_tileset};
+use good_lp::ResolutionError;
+use model::{compute_model, compute_model_with_variables, variables::Variables};
+pub use report::ValidationReport;
 
-use crate::{utils::{get_n, DominoError, Model, Puzzle, ResultTranslator}, Solution, Tile};
+use crate::{
+    solve_puzzle,
+    utils::{DominoError, Model, ModelBuilder, Puzzle, ResultTranslator, TileSet, Topology},
+    Solution, Tile,
+};
 
 mod model;
+mod report;
+
+/// Validates a given puzzle solution, reporting whether the puzzle is unique.
+///
+/// This is a shorthand for `validate_puzzle_report` that maps every verdict but
+/// `ValidationReport::Unique` to the matching `DominoError`.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` structure representing the puzzle to be validated.
+/// * `solution` - A reference to the `Solution` structure representing the proposed solution.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the solution is the only completion of the puzzle.
+/// * `Err(DominoError::AmbiguousPuzzle)` - If another completion exists, with the positions where it differs.
+/// * `Err(DominoError::UnsolvablePuzzle)` - If the puzzle has no completion.
+/// * `Err(DominoError::MalformedPuzzle)` - If the puzzle or the solution are malformed.
+/// * `Err(DominoError::ModelError)` - If the model execution fails.
+pub fn validate_puzzle(puzzle: &Puzzle, solution: &Solution) -> Result<(), DominoError> {
+    validate_puzzle_report(puzzle, solution)?.into_result()
+}
+
+/// Validates a solution of a puzzle of the given `Topology`, reporting whether the puzzle is unique.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` structure representing the puzzle to be validated.
+/// * `solution` - A reference to the `Solution` structure representing the proposed solution.
+/// * `topology` - The `Topology` of the puzzle and of its solution.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the solution is the only completion of the puzzle.
+/// * `Err(DominoError)` - If the puzzle is not unique, as for `validate_puzzle`.
+pub fn validate_puzzle_with_topology(
+    puzzle: &Puzzle,
+    solution: &Solution,
+    topology: Topology,
+) -> Result<(), DominoError> {
+    validate_puzzle_report_with_topology(puzzle, solution, topology)?.into_result()
+}
+
+/// Validates a solution of a puzzle made of the tiles of an explicit `TileSet`, reporting whether the
+/// puzzle is unique.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` structure representing the puzzle to be validated.
+/// * `solution` - A reference to the `Solution` structure representing the proposed solution.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle and of its solution.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the solution is the only completion of the puzzle.
+/// * `Err(DominoError)` - If the puzzle is not unique, as for `validate_puzzle`.
+pub fn validate_puzzle_with_tileset(
+    puzzle: &Puzzle,
+    solution: &Solution,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<(), DominoError> {
+    validate_puzzle_report_with_tileset(puzzle, solution, tileset, topology)?.into_result()
+}
+
+/// Checks that a puzzle is uniquely solvable without requiring a reference solution.
+///
+/// The puzzle is solved once, then the solution found is used as the reference to prove that
+/// no second distinct completion exists.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` structure representing the puzzle to be checked.
+///
+/// # Returns
+///
+/// * `Ok(Solution)` - The only completion of the puzzle.
+/// * `Err(DominoError::AmbiguousPuzzle)` - If a second completion exists, with the positions where it differs.
+/// * `Err(DominoError::UnsolvablePuzzle)` - If the puzzle has no completion.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length is invalid.
+/// * `Err(DominoError::ModelError)` - If the model execution fails.
+pub fn check_uniqueness(puzzle: &Puzzle) -> Result<Solution, DominoError> {
+    let solution = solve_puzzle(puzzle)?;
+    match validate_puzzle_report(puzzle, &solution)? {
+        // The model only links the empty positions, so a completion that breaks the givens means none exists
+        ValidationReport::Malformed(_) => Err(DominoError::UnsolvablePuzzle),
+        report => report.into_result().map(|_| solution),
+    }
+}
 
 /// Validates a given puzzle solution by computing a model and checking the objective value.
 ///
 /// This function takes a reference to a `Puzzle` and a `Solution`, then performs the following steps:
-/// - Computes a string-based model representation using `compute_model()`.
-/// - Executes the computed model using `Model::execute()`.
+/// - Checks that the solution is a completion of the puzzle.
+/// - Computes the model of the puzzle using `compute_model()`.
+/// - Executes the computed model using `Model::execute_builder()`.
 /// - Extracts the objective value from the solver result.
-/// - Compares the objective value against the expected missing tile count.
-/// - Returns `Ok(())` if the objective value matches the missing tile count, otherwise returns a `DominoError`.
+/// - Compares the objective value against the expected missing tile count: when they differ the model
+///   found an alternative completion, which is returned with the positions where it diverges.
 ///
 /// # Arguments
 ///
@@ -20,77 +117,230 @@ mod model;
 ///
 /// # Returns
 ///
-/// * `Ok(())` - If the computed objective value matches the expected number of missing tiles.
-/// * `Err(DominoError::ModelError)` - If the model execution fails or the objective value is incorrect.
+/// * `Ok(ValidationReport)` - The verdict of the validation.
+/// * `Err(DominoError::ModelError)` - If the model execution fails.
+pub fn validate_puzzle_report(
+    puzzle: &Puzzle,
+    solution: &Solution,
+) -> Result<ValidationReport, DominoError> {
+    validate_puzzle_report_with_topology(puzzle, solution, Topology::Closed)
+}
+
+/// Validates a solution of a puzzle of the given `Topology`, as `validate_puzzle_report` does.
 ///
-/// # Errors
+/// # Arguments
 ///
-/// This function returns a `DominoError::ModelError` in the following cases:
-/// - If `compute_model()` returns an error.
-/// - If `Model::execute()` fails to execute the computed model.
-/// - If the extracted objective value does not match the expected missing tile count.
-pub fn validate_puzzle(puzzle: &Puzzle, solution: &Solution) -> Result<(), DominoError> {
-    // Compute a string-based model representation for the puzzle and solution.
-    let string_model = compute_model(puzzle, solution)?;
-    // println!("string_model: {string_model}");
+/// * `puzzle` - A reference to the `Puzzle` structure representing the puzzle to be validated.
+/// * `solution` - A reference to the `Solution` structure representing the proposed solution.
+/// * `topology` - The `Topology` of the puzzle and of its solution.
+///
+/// # Returns
+///
+/// * `Ok(ValidationReport)` - The verdict of the validation.
+/// * `Err(DominoError::ModelError)` - If the model execution fails.
+pub fn validate_puzzle_report_with_topology(
+    puzzle: &Puzzle,
+    solution: &Solution,
+    topology: Topology,
+) -> Result<ValidationReport, DominoError> {
+    match TileSet::for_puzzle(puzzle, topology) {
+        Ok(tileset) => validate_puzzle_report_with_tileset(puzzle, solution, &tileset, topology),
+        Err(error) => Ok(ValidationReport::Malformed(error.to_string())),
+    }
+}
+
+/// Validates a solution of a puzzle made of the tiles of an explicit `TileSet`, as `validate_puzzle_report` does.
+///
+/// A solution not placing exactly the tiles of the set is malformed.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` structure representing the puzzle to be validated.
+/// * `solution` - A reference to the `Solution` structure representing the proposed solution.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle and of its solution.
+///
+/// # Returns
+///
+/// * `Ok(ValidationReport)` - The verdict of the validation.
+/// * `Err(DominoError::ModelError)` - If the model execution fails.
+pub fn validate_puzzle_report_with_tileset(
+    puzzle: &Puzzle,
+    solution: &Solution,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<ValidationReport, DominoError> {
+    if let Err(reason) = check_solution(puzzle, solution, tileset, topology) {
+        return Ok(ValidationReport::Malformed(reason));
+    }
+
+    // Compute the model of the puzzle and solution.
+    let (model, variables) =
+        match compute_model_with_variables(puzzle, solution, tileset, topology) {
+            Ok(computed) => computed,
+            Err(error) => return Ok(ValidationReport::Malformed(error.to_string())),
+        };
     // Execute the model to obtain a solver result.
-    let solver_result = Model::execute(string_model.clone());
-
-    // Extract the objective value from the solver result.
-    // May also see the values of the variables through translator._get_variables() method
-    if let Ok(translator) = solver_result {
-        // Count the number of missing tiles in the puzzle.
-        let missing_tiles = puzzle.0.iter().filter(|tile| tile.is_none()).count() as f64;
-
-        // Validate the objective value against the expected missing tiles count.
-        let objective = translator.get_objective();
-        if objective == missing_tiles {
-            Ok(())
-        } else {
-            let solution: Vec<Option<Tile>> = model_solution_parse(translator, puzzle).expect("Failed to parse solution");
-            Err(DominoError::ModelError(
-                  format!("Found another solution: {solution:?}")// with model: {string_model}"),
-              ))
+    let translator = match Model::execute_builder(&model) {
+        Ok(translator) => translator,
+        Err(ResolutionError::Infeasible) => return Ok(ValidationReport::Unsolvable),
+        Err(_) => {
+            return Err(DominoError::ModelError(
+                "Model failed execution".to_string(),
+            ))
+        }
+    };
+
+    // Count the number of missing tiles in the puzzle.
+    let missing_tiles = puzzle.0.iter().filter(|tile| tile.is_none()).count() as f64;
+
+    // Validate the objective value against the expected missing tiles count.
+    if translator.get_objective() == missing_tiles {
+        return Ok(ValidationReport::Unique);
+    }
+
+    let alternative: Vec<Option<Tile>> = model_solution_parse(translator, puzzle, &variables);
+    let differing_positions: Vec<usize> = alternative
+        .iter()
+        .zip(solution.iter())
+        .enumerate()
+        .filter_map(|(position, (alternative_tile, tile))| {
+            match alternative_tile {
+                Some(alternative_tile)
+                    if alternative_tile.0 == tile.0 && alternative_tile.1 == tile.1 =>
+                {
+                    None
+                }
+                _ => Some(position),
+            }
+        })
+        .collect();
+    Ok(ValidationReport::Ambiguous {
+        alternative,
+        differing_positions,
+    })
+}
+
+/// Builds the linear model executed to validate a puzzle and its solution.
+///
+/// The model minimizes the number of tiles of the solution that a completion of the puzzle leaves out,
+/// and can be exported with `ModelBuilder::to_lp` or `ModelBuilder::to_mps` to inspect it with other tools.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` to validate.
+/// * `solution` - A reference to the `Solution` of the puzzle.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle and of its solution.
+///
+/// # Returns
+///
+/// * `Ok(ModelBuilder)` - The validation model.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle length differs from the size of the set.
+pub fn validate_model(
+    puzzle: &Puzzle,
+    solution: &Solution,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<ModelBuilder, DominoError> {
+    compute_model(puzzle, solution, tileset, topology)
+}
+
+/// Checks that the solution is a domino chain of the given topology completing the puzzle with the tiles of the set.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the `Puzzle` being validated.
+/// * `solution` - A reference to the proposed `Solution`.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the chain, an open one not linking its last tile to the first.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the solution completes the puzzle.
+/// * `Err(String)` - The reason why the puzzle or the solution are malformed.
+fn check_solution(
+    puzzle: &Puzzle,
+    solution: &Solution,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<(), String> {
+    if puzzle.len() != tileset.len() {
+        return Err(format!(
+            "The puzzle has {} positions while the tileset has {} tiles",
+            puzzle.len(),
+            tileset.len()
+        ));
+    }
+    if solution.len() != puzzle.len() {
+        return Err(format!(
+            "The solution has {} tiles while the puzzle has {} positions",
+            solution.len(),
+            puzzle.len()
+        ));
+    }
+    for (position, tile) in solution.iter().enumerate() {
+        if let Some(given) = puzzle[position] {
+            if given.0 != tile.0 || given.1 != tile.1 {
+                return Err(format!(
+                    "The solution places {} at position {} where the puzzle has {}",
+                    tile, position, given
+                ));
+            }
+        }
+        let Some(next) = topology.next(position, solution.len()).map(|next| solution[next]) else {
+            continue;
+        };
+        if tile.1 != next.0 {
+            return Err(format!(
+                "The tiles {} and {} at position {} are not adjacent",
+                tile, next, position
+            ));
         }
-    } else {
-        Err(DominoError::ModelError(
-            "Model failed execution".to_string(),
-        ))
     }
+    if !tileset.is_used_by(solution) {
+        return Err("The solution does not place exactly the tiles of the tileset".to_string());
+    }
+    Ok(())
 }
 
-// Function that given the space of tiles existing for a given puzzle and the result of an lp model having as variables names:
-// x_{i,j} where i is the index of the tile in the tileset used and j the position where it got inserted within the puzzle space
-// returns the solution computed by the lp model
+/// Reads the completion of the puzzle found by the validation model.
+///
+/// # Arguments
+///
+/// * `translator` - The `ResultTranslator` of the executed model.
+/// * `puzzle` - A reference to the `Puzzle` being validated.
+/// * `variables` - The `Variables` declared in the model, each placing an oriented tile at a position.
+///
+/// # Returns
+///
+/// The tiles of the puzzle, completed with the placements the model selected.
 fn model_solution_parse(
     translator: ResultTranslator,
     puzzle: &Puzzle,
-) -> Result<Vec<Option<Tile>>, DominoError> {
-    let variables: std::collections::HashMap<String, f64> = translator._get_variables();
-    let n: i32 = get_n(puzzle)?;
-    let tileset: Vec<(usize, usize)> = create_tileset(n as usize);
-    let tileset_digits: usize = (tileset.len() as f32).log10().floor() as usize + 1;
-    let sequence_digits: usize = (puzzle.0.len() as f32).log10().floor() as usize + 1;
+    variables: &Variables,
+) -> Vec<Option<Tile>> {
     let mut solution: Vec<Option<Tile>> = puzzle.0.clone();
-    for variable in variables.into_iter().filter(|variable| variable.1 == 1.0) {
-        let variable_label: String = variable.0;
-        let tile_index: usize = variable_label[1..1 + tileset_digits]
-            .parse::<usize>()
-            .unwrap();
-        let position_index: usize = variable_label
-            [1 + tileset_digits..1 + tileset_digits + sequence_digits]
-            .parse::<usize>()
-            .unwrap();
-        solution[position_index] =
-            Some((tileset[tile_index].0 as i32, tileset[tile_index].1 as i32).into());
+    for variable in variables.iter() {
+        if translator.value(variable.id) == Some(1.0) {
+            solution[variable.position] =
+                Some((variable.tile.0 as i32, variable.tile.1 as i32).into());
+        }
     }
-    Ok(solution)
+    solution
 }
 
 #[cfg(test)]
 mod tests {
 
-    use super::validate_puzzle;
+    use super::{
+        check_uniqueness, validate_puzzle, validate_puzzle_report, validate_puzzle_report_with_tileset,
+        validate_puzzle_with_tileset, validate_puzzle_with_topology, ValidationReport,
+    };
+    use crate::{
+        solve_puzzle_with_tileset, solve_puzzle_with_topology, DominoError, Puzzle, Solution,
+        SolveMethod, Tile, TileSet, Topology,
+    };
 
     #[test]
     fn test_validate_valid_puzzle_with_single_hole() {
@@ -232,6 +482,130 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_report_unique() {
+        let puzzle = vec![
+            Some((0, 0).into()),
+            Some((0, 1).into()),
+            Some((1, 1).into()),
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            None,
+            None,
+            None,
+        ];
+        let solution = vec![
+            (0, 0).into(),
+            (0, 1).into(),
+            (1, 1).into(),
+            (1, 2).into(),
+            (2, 2).into(),
+            (2, 3).into(),
+            (3, 3).into(),
+            (3, 0).into(),
+        ];
+        let result = validate_puzzle_report(&puzzle.into(), &solution);
+        assert_eq!(result, Ok(ValidationReport::Unique));
+    }
+
+    #[test]
+    fn test_validate_report_ambiguous() {
+        let puzzle: Puzzle = vec![Some((0, 0).into()), None, None, None, None, None, None, None].into();
+        let solution: Solution = vec![
+            (0, 0).into(),
+            (0, 1).into(),
+            (1, 1).into(),
+            (1, 2).into(),
+            (2, 2).into(),
+            (2, 3).into(),
+            (3, 3).into(),
+            (3, 0).into(),
+        ];
+        let result = validate_puzzle_report(&puzzle, &solution).unwrap();
+        println!("Validation report: {:?}", result);
+        match result {
+            ValidationReport::Ambiguous {
+                alternative,
+                differing_positions,
+            } => {
+                assert!(!differing_positions.is_empty());
+                assert_eq!(alternative[0], Some((0, 0).into()));
+                for position in 0..alternative.len() {
+                    let tile = alternative[position].unwrap();
+                    let next = alternative[(position + 1) % alternative.len()].unwrap();
+                    assert_eq!(tile.1, next.0);
+                    let same = tile.0 == solution[position].0 && tile.1 == solution[position].1;
+                    assert_eq!(same, !differing_positions.contains(&position));
+                }
+            }
+            report => panic!("Expected an ambiguous report, found {:?}", report),
+        }
+    }
+
+    #[test]
+    fn test_validate_report_malformed_solution() {
+        let puzzle: Puzzle = vec![Some((0, 1).into()), None, None, None, None, None, None, None].into();
+        let solution: Solution = vec![
+            (0, 0).into(),
+            (0, 1).into(),
+            (1, 1).into(),
+            (1, 2).into(),
+            (2, 2).into(),
+            (2, 3).into(),
+            (3, 3).into(),
+            (3, 0).into(),
+        ];
+        let result = validate_puzzle_report(&puzzle, &solution);
+        assert!(matches!(result, Ok(ValidationReport::Malformed(_))));
+        assert!(matches!(
+            validate_puzzle(&puzzle, &solution),
+            Err(DominoError::MalformedPuzzle(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_uniqueness_unique_puzzle() {
+        let puzzle: Puzzle = vec![
+            None,
+            Some((0, 1).into()),
+            None,
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            None,
+            None,
+            None,
+        ]
+        .into();
+        let solution = check_uniqueness(&puzzle).unwrap();
+        let expected: Solution = vec![
+            (0, 0).into(),
+            (0, 1).into(),
+            (1, 1).into(),
+            (1, 2).into(),
+            (2, 2).into(),
+            (2, 3).into(),
+            (3, 3).into(),
+            (3, 0).into(),
+        ];
+        assert!(solution
+            .iter()
+            .zip(expected.iter())
+            .all(|(tile, expected)| tile.0 == expected.0 && tile.1 == expected.1));
+    }
+
+    #[test]
+    fn test_check_uniqueness_ambiguous_puzzle() {
+        let puzzle: Puzzle = vec![Some((0, 0).into()), None, None, None, None, None, None, None].into();
+        let result = check_uniqueness(&puzzle);
+        assert!(matches!(result, Err(DominoError::AmbiguousPuzzle { .. })));
+    }
+
+    #[test]
+    fn test_check_uniqueness_unsolvable_puzzle() {
+        let puzzle: Puzzle = vec![Some((0, 1).into()), Some((2, 2).into()), None, None, None, None, None, None].into();
+        assert_eq!(check_uniqueness(&puzzle), Err(DominoError::UnsolvablePuzzle));
+    }
+
     #[test]
     fn test_validate_puzzle_with_ambiguous_solution() {
         let puzzle = vec![
@@ -257,6 +631,52 @@ mod tests {
         println!("Testing puzzle with an ambiguous solution: {:?}", puzzle);
         let result = validate_puzzle(&puzzle.into(), &solution);
         println!("Validation result: {:?}", result);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(DominoError::AmbiguousPuzzle { .. })));
+    }
+
+    #[test]
+    fn test_validate_open_puzzle() {
+        // The open tileset of size 3 keeps [0,2], so the line runs from 0 to 2 without closing
+        let puzzle: Puzzle = vec![
+            Some((0, 0).into()),
+            Some((0, 1).into()),
+            Some((1, 1).into()),
+            Some((1, 2).into()),
+            Some((2, 2).into()),
+            Some((2, 3).into()),
+            None,
+            None,
+            Some((0, 2).into()),
+        ]
+        .into();
+        let solution = solve_puzzle_with_topology(&puzzle, SolveMethod::Model, Topology::Open).unwrap();
+        assert_eq!((solution[6].0, solution[6].1), (3, 3));
+        assert_eq!((solution[7].0, solution[7].1), (3, 0));
+        assert!(validate_puzzle_with_topology(&puzzle, &solution, Topology::Open).is_ok());
+        assert!(matches!(
+            validate_puzzle_report(&puzzle, &solution),
+            Ok(ValidationReport::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_tileset_puzzle() {
+        let tileset = TileSet::new(vec![Tile(0, 0), Tile(0, 1), Tile(0, 1), Tile(1, 1)]).unwrap();
+        let puzzle: Puzzle = vec![Some((0, 0).into()), None, Some((1, 1).into()), None].into();
+        let solution =
+            solve_puzzle_with_tileset(&puzzle, SolveMethod::Model, &tileset, Topology::Closed).unwrap();
+        assert_eq!(solution, vec![Tile(0, 0), Tile(0, 1), Tile(1, 1), Tile(1, 0)]);
+        assert!(validate_puzzle_with_tileset(&puzzle, &solution, &tileset, Topology::Closed).is_ok());
+
+        // A completion using a tile the set does not have is malformed
+        let tileset = TileSet::new(vec![Tile(0, 0), Tile(0, 1), Tile(1, 1), Tile(1, 1)]).unwrap();
+        assert!(matches!(
+            validate_puzzle_report_with_tileset(&puzzle, &solution, &tileset, Topology::Closed),
+            Ok(ValidationReport::Malformed(_))
+        ));
+        assert_eq!(
+            solve_puzzle_with_tileset(&puzzle, SolveMethod::Model, &tileset, Topology::Closed),
+            Err(DominoError::UnsolvablePuzzle)
+        );
     }
 }
@@ -1,50 +1,51 @@
 //! This module defines constraints for the optimization model, ensuring valid tile placement and adjacency rules.
-
 use super::{
-    helpers::{collect_labels, create_bound_string},
-    stringify_variables,
+    helpers::{collect_ids, create_bound},
     variables::{Variable, Variables},
     Puzzle,
 };
-use crate::utils::{get_n, Tile};
+use crate::utils::{
+    ConstraintSense, LinearConstraint, LinearExpression, Tile, TileSet, Topology, VariableId,
+};
 
 /// Generates constraints ensuring each tile is used exactly once.
 ///
 /// This function enforces that:
-/// - Each tile appears exactly once in the puzzle.
+/// - Each tile appears in the puzzle exactly as many times as it has copies in the tileset.
 /// - If a tile has different values (e.g., `(a, b)` vs `(b, a)`), both orientations are considered together.
 ///
 /// # Arguments
 ///
 /// * `vars` - A reference to the `Variables` structure containing decision variables.
+/// * `tileset` - The `TileSet` the puzzle is made of.
 ///
 /// # Returns
 ///
-/// A vector of strings representing the constraints.
-pub fn each_tile_used_once_bound(vars: &Variables) -> Vec<String> {
+/// A vector of `LinearConstraint` representing the constraints.
+pub fn each_tile_used_once_bound(vars: &Variables, tileset: &TileSet) -> Vec<LinearConstraint> {
     let mut prob_bounds = Vec::new();
 
     for variables in vars.by_tile.values() {
         // Directly unwrap the first variable to get the tile
         // This fails only if there are no variables for the tile
         let first_var = variables.get(0).unwrap();
-        let mut remapped_vars = collect_labels(variables);
+        let mut remapped_vars = collect_ids(variables);
 
         // Enforce that a tile `(a, b)` does not appear in reverse as `(b, a)`
         if first_var.tile.0 != first_var.tile.1 {
             let rotated_tile = (first_var.tile.1, first_var.tile.0);
             let empty_vec = vec![];
             let unwrapped_rotated_vars = vars.by_tile.get(&rotated_tile).unwrap_or(&empty_vec);
-            remapped_vars.extend(collect_labels(unwrapped_rotated_vars));
+            remapped_vars.extend(collect_ids(unwrapped_rotated_vars));
         }
 
         if !remapped_vars.is_empty() {
-            let bound = create_bound_string(remapped_vars);
+            let copies = tileset.count(Tile(first_var.tile.0 as i32, first_var.tile.1 as i32));
+            let bound = create_bound(remapped_vars, copies);
             prob_bounds.push(bound);
         }
     }
 
-    prob_bounds.push(String::new()); // Ensure proper formatting with a newline
     prob_bounds
 }
 
@@ -58,17 +59,16 @@ pub fn each_tile_used_once_bound(vars: &Variables) -> Vec<String> {
 ///
 /// # Returns
 ///
-/// A vector of strings representing the constraints.
-pub fn each_position_filled_bound(vars: &Variables) -> Vec<String> {
+/// A vector of `LinearConstraint` representing the constraints.
+pub fn each_position_filled_bound(vars: &Variables) -> Vec<LinearConstraint> {
     let mut prob_bounds = Vec::new();
 
     for variables in vars.by_position.values() {
-        let labels = collect_labels(variables);
-        let bound = create_bound_string(labels);
+        let ids = collect_ids(variables);
+        let bound = create_bound(ids, 1);
         prob_bounds.push(bound);
     }
 
-    prob_bounds.push(String::new()); // Ensure proper formatting with a newline
     prob_bounds
 }
 
@@ -80,32 +80,46 @@ pub fn each_position_filled_bound(vars: &Variables) -> Vec<String> {
 ///
 /// * `puzzle` - A reference to the `Puzzle` structure representing the puzzle configuration.
 /// * `vars` - A reference to the `Variables` structure containing decision variables.
+/// * `n` - The largest value of the tiles of the puzzle.
+/// * `topology` - The `Topology` of the puzzle, the last position of an open line having no next position.
 ///
 /// # Returns
 ///
-/// A vector of strings representing adjacency constraints.
-pub fn next_adjacent_bound(puzzle: &Puzzle, vars: &Variables) -> Vec<String> {
+/// A vector of `LinearConstraint` representing adjacency constraints.
+pub fn next_adjacent_bound(
+    puzzle: &Puzzle,
+    vars: &Variables,
+    n: usize,
+    topology: Topology,
+) -> Vec<LinearConstraint> {
     let mut prob_bounds = Vec::new();
-    let n = get_n(puzzle).expect("Puzzle does not have a valid length") as usize;
 
     for position in 0..puzzle.0.len() {
-        if puzzle.0[position].is_some() {
+        let Some(next_position) = topology.next(position, puzzle.0.len()) else {
             continue;
-        }
-        if let Some(tile) = puzzle.0[(position + 1) % puzzle.0.len()] {
-            if let Some(bound) = next_enforced_bound(vars, tile, position) {
-                prob_bounds.push(bound);
+        };
+        match (puzzle.0[position], puzzle.0[next_position]) {
+            (Some(_), Some(_)) => {}
+            (Some(tile), None) => {
+                if let Some(bound) = previous_enforced_bound(vars, tile, next_position) {
+                    prob_bounds.push(bound);
+                }
             }
-        } else {
-            for number in 0..=n {
-                if let Some(bound) = next_bound(puzzle, vars, position, number) {
+            (None, Some(tile)) => {
+                if let Some(bound) = next_enforced_bound(vars, tile, position) {
                     prob_bounds.push(bound);
                 }
             }
+            (None, None) => {
+                for number in 0..=n {
+                    if let Some(bound) = next_bound(vars, position, next_position, number) {
+                        prob_bounds.push(bound);
+                    }
+                }
+            }
         }
     }
 
-    prob_bounds.push(String::new()); // Ensure proper formatting with a newline
     prob_bounds
 }
 
@@ -121,19 +135,44 @@ pub fn next_adjacent_bound(puzzle: &Puzzle, vars: &Variables) -> Vec<String> {
 ///
 /// # Returns
 ///
-/// An `Option<String>` containing the adjacency constraint if applicable.
-fn next_enforced_bound(vars: &Variables, tile: Tile, position: usize) -> Option<String> {
+/// An `Option<LinearConstraint>` containing the adjacency constraint if applicable.
+fn next_enforced_bound(vars: &Variables, tile: Tile, position: usize) -> Option<LinearConstraint> {
     let number = tile.0 as usize;
     let condition = |var: &Variable| var.tile.1 == number;
-    let left_member_variables: Vec<String> =
+    let left_member_variables: Vec<VariableId> =
         variables_at_position_with_condition(vars, position, condition);
 
     if left_member_variables.is_empty() {
         return None;
     }
 
-    let bound = format!("{} = 1", stringify_variables!(left_member_variables, " + "));
-    Some(bound)
+    Some(create_bound(left_member_variables, 1))
+}
+
+/// Generates a constraint enforcing adjacency when a tile is already placed at the previous position.
+///
+/// This function ensures that the tile following a given starts with the right value of the given.
+///
+/// # Arguments
+///
+/// * `vars` - A reference to the `Variables` structure containing decision variables.
+/// * `tile` - The `Tile` that is already placed at the previous position.
+/// * `position` - The current position in the puzzle.
+///
+/// # Returns
+///
+/// An `Option<LinearConstraint>` containing the adjacency constraint if applicable.
+fn previous_enforced_bound(vars: &Variables, tile: Tile, position: usize) -> Option<LinearConstraint> {
+    let number = tile.1 as usize;
+    let condition = |var: &Variable| var.tile.0 == number;
+    let right_member_variables: Vec<VariableId> =
+        variables_at_position_with_condition(vars, position, condition);
+
+    if right_member_variables.is_empty() {
+        return None;
+    }
+
+    Some(create_bound(right_member_variables, 1))
 }
 
 /// Generates a constraint enforcing adjacency when the next tile is unknown.
@@ -142,39 +181,43 @@ fn next_enforced_bound(vars: &Variables, tile: Tile, position: usize) -> Option<
 ///
 /// # Arguments
 ///
-/// * `puzzle` - A reference to the `Puzzle` structure representing the puzzle configuration.
 /// * `vars` - A reference to the `Variables` structure containing decision variables.
 /// * `position` - The current position in the puzzle.
+/// * `next_position` - The position following `position`.
 /// * `number` - The expected adjacent tile number.
 ///
 /// # Returns
 ///
-/// An `Option<String>` containing the adjacency constraint if applicable.
-fn next_bound(puzzle: &Puzzle, vars: &Variables, position: usize, number: usize) -> Option<String> {
+/// An `Option<LinearConstraint>` containing the adjacency constraint if applicable.
+fn next_bound(
+    vars: &Variables,
+    position: usize,
+    next_position: usize,
+    number: usize,
+) -> Option<LinearConstraint> {
     let condition = |var: &Variable| var.tile.1 == number;
-    let left_member_variables: Vec<String> =
+    let left_member_variables: Vec<VariableId> =
         variables_at_position_with_condition(vars, position, condition);
 
-    let next_position = (position + 1) % puzzle.0.len();
     let condition = |var: &Variable| var.tile.0 == number;
-    let right_member_variables: Vec<String> =
+    let right_member_variables: Vec<VariableId> =
         variables_at_position_with_condition(vars, next_position, condition);
 
     if left_member_variables.is_empty() {
         return None;
     }
 
-    let bound = format!(
-        "{} - {} = 0",
-        stringify_variables!(left_member_variables, " + "),
-        stringify_variables!(right_member_variables, " - ")
-    );
-    Some(bound)
+    // The tiles ending with `number` at `position` are as many as the ones starting with it at `next_position`
+    let mut expression = LinearExpression::sum(left_member_variables);
+    for variable in right_member_variables {
+        expression.add_term(-1.0, variable);
+    }
+    Some(LinearConstraint::new(expression, ConstraintSense::Equal, 0.0))
 }
 
 /// Filters variables at a given position based on a specified condition.
 ///
-/// This function retrieves the variable labels from a given position that satisfy the provided condition.
+/// This function retrieves the variables from a given position that satisfy the provided condition.
 ///
 /// # Arguments
 ///
@@ -184,21 +227,16 @@ fn next_bound(puzzle: &Puzzle, vars: &Variables, position: usize, number: usize)
 ///
 /// # Returns
 ///
-/// A vector of `String` labels representing the variables that satisfy the condition.
+/// A vector of the `VariableId` of the variables that satisfy the condition.
 fn variables_at_position_with_condition(
     vars: &Variables,
     position: usize,
     condition: impl Fn(&Variable) -> bool,
-) -> Vec<String> {
-    vars.clone()
-        .by_position
+) -> Vec<VariableId> {
+    vars.by_position
         .get(&position)
-        .unwrap_or(&vec![])
-        .clone()
-        .into_iter()
-        .filter(|var| condition(var))
-        .map(|var| var.label)
-        .collect()
+        .map(|variables| variables.iter().filter(|var| condition(var)).map(|var| var.id).collect())
+        .unwrap_or_default()
 }
 
 /// Generates a constraint ensuring the tiles of the puzzle are set to one.
@@ -212,26 +250,19 @@ fn variables_at_position_with_condition(
 ///
 /// # Returns
 ///
-/// A vector of strings representing the constraint.
-pub fn partial_tiles_bound(puzzle: &Puzzle, vars: &Variables) -> Vec<String> {
+/// A vector of `LinearConstraint` representing the constraint.
+pub fn partial_tiles_bound(puzzle: &Puzzle, vars: &Variables) -> Vec<LinearConstraint> {
     let mut prob_bounds = Vec::new();
 
     for (index, tile) in puzzle.0.iter().enumerate() {
         if let Some(tile) = tile {
-
-            let variables = vars
-            .by_tile.get(&(tile.0 as usize, tile.1 as usize));
-
-            if let Some(variables) = variables {
-              let first = variables.iter().filter(|variable| variable.position == index)
-              .next();
-              if let Some(tile) = first {
-                let label = tile.label.clone();
-                prob_bounds.push(format!("{} = 1", label));
-              }
+            let variable = vars
+                .by_tile
+                .get(&(tile.0 as usize, tile.1 as usize))
+                .and_then(|variables| variables.iter().find(|variable| variable.position == index));
+            if let Some(variable) = variable {
+                prob_bounds.push(create_bound(vec![variable.id], 1));
             }
-
-
         }
     }
 
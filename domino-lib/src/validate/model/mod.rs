@@ -4,8 +4,8 @@
 //! using mathematical optimization principles.
 
 use bounds::partial_tiles_bound;
-use crate::{stringify_variables, Solution};
-use crate::utils::{DominoError, Puzzle};
+use crate::Solution;
+use crate::utils::{DominoError, LinearConstraint, LinearExpression, ModelBuilder, Puzzle, TileSet, Topology};
 
 use super::model::bounds::{
     each_position_filled_bound, each_tile_used_once_bound, next_adjacent_bound,
@@ -27,21 +27,28 @@ pub mod variables;
 ///
 /// * `puzzle` - A reference to the `Puzzle` for which constraints are generated.
 /// * `vars` - A reference to the `Variables` structure containing decision variables.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle.
 ///
 /// # Returns
 ///
-/// A vector of strings representing the mathematical constraints for the optimization model.
-fn bounds(puzzle: &Puzzle, vars: &Variables) -> Vec<String> {
+/// A vector of `LinearConstraint` representing the mathematical constraints for the optimization model.
+fn bounds(
+    puzzle: &Puzzle,
+    vars: &Variables,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Vec<LinearConstraint> {
     let mut prob_bounds = Vec::new();
 
     // Add constraints to ensure each tile is used only once.
-    prob_bounds.extend(each_tile_used_once_bound(vars));
+    prob_bounds.extend(each_tile_used_once_bound(vars, tileset));
 
     // Add constraints to ensure each position in the puzzle is filled.
     prob_bounds.extend(each_position_filled_bound(vars));
 
     // Add constraints to enforce adjacency rules.
-    prob_bounds.extend(next_adjacent_bound(puzzle, vars));
+    prob_bounds.extend(next_adjacent_bound(puzzle, vars, tileset.n(), topology));
 
     // Set the tiles of the puzzle as one's
     prob_bounds.extend(partial_tiles_bound(puzzle, vars));
@@ -63,39 +70,29 @@ fn bounds(puzzle: &Puzzle, vars: &Variables) -> Vec<String> {
 ///
 /// # Returns
 ///
-/// A string representing the linear objective function to be minimized.
-fn objective_function(vars: &Variables, puzzle: &Puzzle, solution: &Solution) -> String {
-  let labels = solution
-      .clone()
-      .into_iter()
-      .enumerate()
-      .filter_map(|(i, tile)| {
-          // Ignore positions that are already occupied in the puzzle.
-          if puzzle.0[i].is_some() {
-              return None;
-          }
-
-          // Convert tile values to usize for indexing.
-          let tile: (usize, usize) = (tile.0.try_into().unwrap(), tile.1.try_into().unwrap());
-
-          // Retrieve the variable corresponding to the tile at the given position.
-          if let Some(tiles) = vars.by_tile.get(&tile) {
-              let variable = tiles.iter().filter(|var| var.position == i).next()?;
-              Some(variable.label.clone())
-          } else {
-              None
-          }
-      })
-      .collect::<Vec<String>>();
-
-  // Convert the list of variable labels into a formatted string representation.
-  let obj = stringify_variables!(labels, " ");
-  obj
+/// The `LinearExpression` of the objective function to be minimized.
+fn objective_function(vars: &Variables, puzzle: &Puzzle, solution: &Solution) -> LinearExpression {
+    let ids = solution.iter().enumerate().filter_map(|(i, tile)| {
+        // Ignore positions that are already occupied in the puzzle.
+        if puzzle.0[i].is_some() {
+            return None;
+        }
+
+        // Retrieve the variable corresponding to the tile at the given position.
+        let tile: (usize, usize) = (tile.0 as usize, tile.1 as usize);
+        vars.by_tile
+            .get(&tile)?
+            .iter()
+            .find(|var| var.position == i)
+            .map(|var| var.id)
+    });
+
+    LinearExpression::sum(ids)
 }
 
 /// Computes a mathematical optimization model for the given puzzle and solution.
 ///
-/// The generated model follows the linear programming (LP) format, including:
+/// The generated model is built directly as a `ModelBuilder`, including:
 /// - **Objective Function:** Minimize the number of missing tiles.
 /// - **Constraints:** Ensure valid tile placement and adjacency.
 /// - **Binary Variables:** Representing the placement of tiles in the puzzle.
@@ -104,47 +101,47 @@ fn objective_function(vars: &Variables, puzzle: &Puzzle, solution: &Solution) ->
 ///
 /// * `puzzle` - A reference to the `Puzzle` structure representing the puzzle configuration.
 /// * `solution` - A reference to the `Solution` structure representing the proposed solution.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `topology` - The `Topology` of the puzzle.
 ///
 /// # Returns
 ///
-/// * `Ok(String)` - A valid optimization model represented as a string.
-/// * `Err(DominoError::ModelError)` - If an error occurs while generating the model.
+/// * `Ok(ModelBuilder)` - The optimization model of the puzzle.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle has not a position for each tile of the set.
+pub fn compute_model(
+    puzzle: &Puzzle,
+    solution: &Solution,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<ModelBuilder, DominoError> {
+    compute_model_with_variables(puzzle, solution, tileset, topology).map(|(model, _)| model)
+}
+
+/// Computes the optimization model of a puzzle and its solution along with its decision variables, as
+/// `compute_model` does.
 ///
-/// # Errors
+/// # Returns
 ///
-/// This function returns an error if:
-/// - The variable generation fails, the puzzle is malformed.
-/// - There is an issue forming the constraints or objective function, the puzzle is malformed.
-pub fn compute_model(puzzle: &Puzzle, solution: &Solution) -> Result<String, DominoError> {
-    // Generate decision variables for the puzzle.
-    let prob_vars = variables(puzzle)?;
+/// * `Ok((ModelBuilder, Variables))` - The model and the variables declared in it.
+/// * `Err(DominoError::InvalidLength)` - If the puzzle has not a position for each tile of the set.
+pub fn compute_model_with_variables(
+    puzzle: &Puzzle,
+    solution: &Solution,
+    tileset: &TileSet,
+    topology: Topology,
+) -> Result<(ModelBuilder, Variables), DominoError> {
+    let mut model = ModelBuilder::new();
+
+    // Generate decision variables for the puzzle, declaring them as binary variables of the model.
+    let prob_vars = variables(puzzle, tileset, &mut model)?;
 
     // Compute the objective function to minimize missing tiles.
-    let prob_obj = objective_function(&prob_vars, puzzle, solution);
+    model.minimize(objective_function(&prob_vars, puzzle, solution));
 
     // Generate constraints (bounds) for valid tile placement.
-    let prob_bounds = bounds(puzzle, &prob_vars);
-
-    // Construct the optimization model in LP format.
-    let mut model = "Minimize\n".to_string();
-    model.push_str(format!(" obj: {}\n", prob_obj).as_str());
-    model.push_str("Subject To\n");
-
-    // Append each constraint with a unique label.
-    for (i, constraint) in prob_bounds.iter().enumerate() {
-        if !constraint.is_empty() {
-            model.push_str(format!(" c{}: {}\n", i, constraint).as_str());
-        }
+    for constraint in bounds(puzzle, &prob_vars, tileset, topology) {
+        model.add_constraint(constraint);
     }
 
-    // Define binary decision variables for tile placement.
-    model.push_str("Binary\n");
-    for variable in prob_vars.vars {
-        model.push_str(format!(" {}\n", variable.label).as_str());
-    }
-
-    // Finalize the model.
-    model.push_str("End");
-
-    Ok(model)
+    Ok((model, prob_vars))
 }
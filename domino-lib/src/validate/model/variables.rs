@@ -3,9 +3,7 @@
 //! It includes structures and functions to manage variables, generate tile combinations,
 //! and construct labeled variables for a given puzzle.
 
-use itertools::Itertools;
-
-use crate::utils::{get_n, DominoError, Puzzle};
+use crate::utils::{DominoError, ModelBuilder, Puzzle, TileSet, VariableId};
 use std::collections::HashMap;
 
 /// Represents a decision variable in the optimization model.
@@ -14,9 +12,11 @@ use std::collections::HashMap;
 /// - A unique `label`
 /// - A `tile` represented as a tuple `(usize, usize)`
 /// - A `position` indicating its placement in the puzzle
+/// - The `id` of the binary variable declared in the model
 #[derive(Debug, Clone)]
 pub struct Variable {
     pub label: String,
+    pub id: VariableId,
     pub tile: (usize, usize),
     pub position: usize,
 }
@@ -79,9 +79,11 @@ impl Variables {
         insert_or_update(&mut self.by_position, variable.position, variable);
     }
 
-    pub fn sort_by_label(&mut self) {
-      self.vars = self.vars.clone().into_iter().sorted_by_key(|v| v.label.clone()).collect();
+    /// Returns an iterator over the variables, in the order they were declared in the model.
+    pub fn iter(&self) -> impl Iterator<Item = &Variable> {
+        self.vars.iter()
     }
+
 }
 
 /// Generates variables for a given puzzle by determining valid tile placements.
@@ -89,96 +91,96 @@ impl Variables {
 /// # Arguments
 ///
 /// * `puzzle` - A reference to the `Puzzle` structure representing the puzzle configuration.
+/// * `tileset` - The `TileSet` the puzzle is made of.
+/// * `model` - The `ModelBuilder` in which the binary variables are declared.
 ///
 /// # Returns
 ///
 /// * `Ok(Variables)` - A `Variables` structure containing all valid decision variables.
-/// * `Err(DominoError::InvalidPuzzle)` - If the puzzle is invalid.
-pub fn variables(puzzle: &Puzzle) -> Result<Variables, DominoError> {
-    let n = get_n(puzzle)? as usize;
+/// * `Err(DominoError::InvalidLength)` - If the puzzle has not a position for each tile of the set.
+pub fn variables(
+    puzzle: &Puzzle,
+    tileset: &TileSet,
+    model: &mut ModelBuilder,
+) -> Result<Variables, DominoError> {
+    if puzzle.len() != tileset.len() {
+        return Err(DominoError::InvalidLength);
+    }
 
-    let tileset: Vec<(usize, (usize, usize))> = create_tileset(n)
+    let tileset_indices = create_tileset(tileset)
         .into_iter()
         .enumerate()
-        .map(|(i, tile)| (i, tile))
         .collect();
 
-    let mapped_variables = generate_combinations(tileset, n)
-        .into_iter()
-        .collect();
+    let mapped_variables = generate_combinations(tileset_indices, tileset.len(), model);
 
-    let mut vars = Variables::new(mapped_variables);
-    vars.sort_by_label();
-    Ok(vars)
+    Ok(Variables::new(mapped_variables))
 }
 
-/// Generates a set of tiles based on a given value `N`.
+/// Generates the oriented tiles of a tileset, each tile in both orientations.
+///
+/// The copies of a tile share the same oriented tiles, so each of them appears once.
 ///
 /// # Arguments
 ///
-/// * `n` - The maximum tile value.
+/// * `tileset` - The `TileSet` the puzzle is made of.
 ///
 /// # Returns
 ///
-/// A vector of tuples representing all valid tiles `(usize, usize)`.
-pub fn create_tileset(n: usize) -> Vec<(usize, usize)> {
-    let length: usize = (n + 1).pow(2);
-    let mut tileset: Vec<(usize, usize)> = (0..length)
-        .map(|i| (i / (n + 1), i % (n + 1)))
-        .collect::<Vec<(usize, usize)>>();
-
-    // Adjust tile selection for odd `n` values
-    if n % 2 == 1 {
-        tileset.retain(|&(i, j)| {
-            !(i <= j && j == i + (n + 1) / 2) && !(i > j && i == j + (n + 1) / 2)
-        });
-    }
+/// A sorted vector of tuples representing all valid oriented tiles `(usize, usize)`.
+pub fn create_tileset(tileset: &TileSet) -> Vec<(usize, usize)> {
+    let mut oriented: Vec<(usize, usize)> = tileset
+        .tiles()
+        .iter()
+        .flat_map(|tile| [(tile.0 as usize, tile.1 as usize), (tile.1 as usize, tile.0 as usize)])
+        .collect();
+    oriented.sort();
+    oriented.dedup();
 
-    tileset
+    oriented
 }
 
 /// Generates variable combinations for a given set of tiles.
 ///
-/// Each tile is assigned a unique label based on its index and position.
+/// Each tile is assigned a unique label based on its index and position. The variables are generated, and
+/// declared in the model, sorted by label.
 ///
 /// # Arguments
 ///
 /// * `tileset` - A vector of tuples containing tile indices and tiles.
-/// * `n` - The maximum tile value.
+/// * `sequence_length` - The number of positions of the puzzle.
+/// * `model` - The `ModelBuilder` in which the binary variables are declared.
 ///
 /// # Returns
 ///
 /// A vector of `Variable` instances representing all possible tile placements.
-fn generate_combinations(tileset: Vec<(usize, (usize, usize))>, n: usize) -> Vec<Variable> {
-    let sequence_length: usize = if n % 2 == 0 {
-        (n + 1) * (n + 2) / 2
-    } else {
-        (n + 1).pow(2) / 2
-    };
-
+fn generate_combinations(
+    tileset: Vec<(usize, (usize, usize))>,
+    sequence_length: usize,
+    model: &mut ModelBuilder,
+) -> Vec<Variable> {
     let tileset_length = tileset.len();
     let tileset_digits = (tileset_length as f32).log10().floor() as usize + 1;
     let sequence_digits = (sequence_length as f32).log10().floor() as usize + 1;
 
-    let positions: Vec<usize> = (0..sequence_length).collect::<Vec<usize>>();
-
-    tileset
-        .iter()
-        .flat_map(|(tile_index, tile)| {
-            positions.iter().map(move |&position| {
-                let label: String = format!(
-                    "x{}{}",
-                    format_on_n_digits(*tile_index, tileset_digits),
-                    format_on_n_digits(position, sequence_digits)
-                );
-                Variable {
-                    label,
-                    tile: *tile,
-                    position,
-                }
-            })
-        })
-        .collect::<Vec<Variable>>()
+    let mut variables = Vec::with_capacity(tileset_length * sequence_length);
+    for (tile_index, tile) in &tileset {
+        for position in 0..sequence_length {
+            let label: String = format!(
+                "x{}{}",
+                format_on_n_digits(*tile_index, tileset_digits),
+                format_on_n_digits(position, sequence_digits)
+            );
+            let id = model.add_binary(label.as_str());
+            variables.push(Variable {
+                label,
+                id,
+                tile: *tile,
+                position,
+            });
+        }
+    }
+    variables
 }
 
 /// Formats a number with leading zeros to match a specified digit width.
@@ -0,0 +1,54 @@
+//! This module defines the `ValidationReport` enum, which describes the outcome of validating a puzzle.
+//!
+//! Unlike a plain error, the report carries the data needed to explain the verdict, such as the
+//! alternative completion of an ambiguous puzzle and the positions where it diverges.
+
+use crate::{DominoError, Tile};
+
+/// The verdict of the validation of a puzzle against a reference solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationReport {
+    /// The reference solution is the only completion of the puzzle.
+    Unique,
+    /// The puzzle has another completion besides the reference solution.
+    Ambiguous {
+        /// The alternative completion found for the puzzle.
+        alternative: Vec<Option<Tile>>,
+        /// The positions where the alternative differs from the reference solution.
+        differing_positions: Vec<usize>,
+    },
+    /// The puzzle has no completion at all.
+    Unsolvable,
+    /// The puzzle or the reference solution are not well formed, the reason is described in the message.
+    Malformed(String),
+}
+
+impl ValidationReport {
+    /// Returns `true` if the puzzle has been proven unique.
+    pub fn is_unique(&self) -> bool {
+        *self == ValidationReport::Unique
+    }
+
+    /// Converts the report into a `Result`, mapping every verdict but `Unique` to the matching `DominoError`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the report is `Unique`.
+    /// * `Err(DominoError::AmbiguousPuzzle)` - If the report is `Ambiguous`.
+    /// * `Err(DominoError::UnsolvablePuzzle)` - If the report is `Unsolvable`.
+    /// * `Err(DominoError::MalformedPuzzle)` - If the report is `Malformed`.
+    pub fn into_result(self) -> Result<(), DominoError> {
+        match self {
+            ValidationReport::Unique => Ok(()),
+            ValidationReport::Ambiguous {
+                alternative,
+                differing_positions,
+            } => Err(DominoError::AmbiguousPuzzle {
+                alternative,
+                differing_positions,
+            }),
+            ValidationReport::Unsolvable => Err(DominoError::UnsolvablePuzzle),
+            ValidationReport::Malformed(reason) => Err(DominoError::MalformedPuzzle(reason)),
+        }
+    }
+}
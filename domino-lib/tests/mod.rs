@@ -4,7 +4,10 @@
 mod tests {
 
   use domino_lib::{
-      classify_puzzle, generate_puzzle, solve_puzzle, validate_puzzle, ComplexityClass,
+      classify_puzzle, classify_puzzle_by_deduction, classify_puzzle_with, generate_batch, generate_minimal, generate_puzzle,
+      generate_puzzle_from_seed, generate_puzzle_with, solve_all, solve_model, solve_puzzle, solve_puzzle_with,
+      validate_model, validate_puzzle, ClassificationScheme, ComplexityClass, ConstraintSense, Model, ModelBuilder, PuzzleGenerator,
+      SolveMethod, TileSet, Topology,
   };
 
   fn test_suite() -> Vec<usize> {
@@ -16,7 +19,7 @@ mod tests {
   fn test_generate() {
       test_suite().into_iter().for_each(|n| {
         (1..=3).for_each(|c| {
-          let puzzle = generate_puzzle(n, c);
+          let puzzle = generate_puzzle(n, c).unwrap();
           if n % 2 == 0 {
               assert_eq!(puzzle.0.len(), (n + 1) * (n + 2) / 2);
           } else {
@@ -26,11 +29,62 @@ mod tests {
       });
   }
 
+  #[test]
+  fn test_generate_from_seed() {
+      test_suite().into_iter().for_each(|n| {
+        (1..=3).for_each(|c| {
+          let puzzle = generate_puzzle_from_seed(n, c, 2024).unwrap();
+          let same_puzzle = generate_puzzle_from_seed(n, c, 2024).unwrap();
+          assert_eq!(
+              serde_json::to_string(&puzzle).unwrap(),
+              serde_json::to_string(&same_puzzle).unwrap()
+          );
+        });
+      });
+  }
+
+  #[test]
+  fn test_generate_with_generator() {
+      test_suite().into_iter().for_each(|n| {
+        let puzzle = PuzzleGenerator::new(n)
+            .with_holes(2)
+            .with_seed(2024)
+            .with_doubles(false)
+            .generate()
+            .unwrap();
+        let solution = solve_puzzle(&puzzle).unwrap();
+        assert!(validate_puzzle(&puzzle, &solution).is_ok());
+        assert!(puzzle.0.iter().flatten().filter(|tile| tile.0 == tile.1).count() == n + 1);
+      });
+  }
+
+  #[test]
+  fn test_generate_batch() {
+      test_suite().into_iter().for_each(|n| {
+        let batch = generate_batch(&PuzzleGenerator::new(n).with_class(1), 4).unwrap();
+        assert_eq!(batch.len(), 4);
+        batch.iter().for_each(|generated| {
+          let solution = solve_puzzle(&generated.puzzle).unwrap();
+          assert!(validate_puzzle(&generated.puzzle, &solution).is_ok());
+          assert_eq!(generated.class, Some(ComplexityClass(1)));
+        });
+      });
+  }
+
+  #[test]
+  fn test_generate_minimal() {
+      test_suite().into_iter().for_each(|n| {
+        let minimal = generate_minimal(n, 2024).unwrap();
+        assert!(minimal.every_given_necessary);
+        assert_eq!(solve_all(&minimal.puzzle, 2).unwrap().count(), 1);
+      });
+  }
+
   #[test]
   fn test_solve() {
       test_suite().into_iter().for_each(|n| {
         (1..=3).for_each(|c| {
-          let puzzle = generate_puzzle(n, c);
+          let puzzle = generate_puzzle(n, c).unwrap();
           println!("Puzzle: {puzzle:?}");
           let solution = solve_puzzle(&puzzle).unwrap();
           println!("Solution: {solution:?}");
@@ -39,12 +93,36 @@ mod tests {
     });
   }
 
+  #[test]
+  fn test_solve_backtracking() {
+      test_suite().into_iter().for_each(|n| {
+        (1..=3).for_each(|c| {
+          let puzzle = generate_puzzle(n, c).unwrap();
+          let solution = solve_puzzle_with(&puzzle, SolveMethod::Backtracking).unwrap();
+          assert_eq!(solution.len(), puzzle.0.len());
+          assert!(validate_puzzle(&puzzle, &solution).is_ok());
+      });
+    });
+  }
+
+  #[test]
+  fn test_solve_all() {
+      test_suite().into_iter().for_each(|n| {
+        (1..=3).for_each(|c| {
+          // Generated puzzles are unique, so a second solution is never found
+          let puzzle = generate_puzzle(n, c).unwrap();
+          let solutions: Vec<_> = solve_all(&puzzle, 2).unwrap().collect();
+          assert_eq!(solutions.len(), 1);
+      });
+    });
+  }
+
   #[test]
   fn test_validate() {
       test_suite().into_iter().for_each(|n| {
         (1..=3).for_each(|c| {
           // For each length a puzzle with a single tile missing is always valid
-          let puzzle = generate_puzzle(n, c);
+          let puzzle = generate_puzzle(n, c).unwrap();
           println!("Puzzle: {}",serde_json::to_string(&puzzle).unwrap());
           let solution = solve_puzzle(&puzzle).unwrap();
           let result = validate_puzzle(&puzzle, &solution);
@@ -53,6 +131,52 @@ mod tests {
     });
   }
 
+  /// The constraints of a model, with their terms sorted by variable name.
+  fn normalized(model: &ModelBuilder) -> Vec<(String, Vec<(String, f64)>, ConstraintSense, f64)> {
+      model
+          .constraints()
+          .iter()
+          .map(|constraint| {
+              let mut terms: Vec<(String, f64)> = constraint
+                  .expression()
+                  .terms()
+                  .iter()
+                  .map(|(coefficient, variable)| (model.variable_name(*variable).to_string(), *coefficient))
+                  .collect();
+              terms.sort_by(|a, b| a.0.cmp(&b.0));
+              (constraint.name().to_string(), terms, constraint.sense(), constraint.rhs())
+          })
+          .collect()
+  }
+
+  #[test]
+  fn test_model_export() {
+      test_suite().into_iter().for_each(|n| {
+          let puzzle = generate_puzzle(n, 2).unwrap();
+          let solution = solve_puzzle(&puzzle).unwrap();
+          let tileset = TileSet::regular(n);
+          let models = [
+              solve_model(&puzzle, &tileset, Topology::Closed).unwrap(),
+              validate_model(&puzzle, &solution, &tileset, Topology::Closed).unwrap(),
+          ];
+          models.iter().for_each(|model| {
+              let lp = model.to_lp();
+              let mps = model.to_mps();
+              let from_mps = ModelBuilder::from_mps(&mps).unwrap();
+              assert_eq!(from_mps.to_mps(), mps);
+              assert_eq!(normalized(&from_mps), normalized(model));
+              assert_eq!(
+                  normalized(&ModelBuilder::from_lp(&from_mps.to_lp()).unwrap()),
+                  normalized(&ModelBuilder::from_lp(&lp).unwrap())
+              );
+
+              let from_lp = Model::execute(lp).unwrap();
+              let from_mps = Model::execute_mps(mps).unwrap();
+              assert_eq!(from_lp.get_objective(), from_mps.get_objective());
+          });
+      });
+  }
+
   #[test]
   fn test_classify() {
       test_suite().into_iter().for_each(|n| {
@@ -60,7 +184,7 @@ mod tests {
               .into_iter()
               .map(|c| ComplexityClass::new(c).unwrap())
               .for_each(|expected_complexity| {
-                  let puzzle = generate_puzzle(n, expected_complexity.0);
+                  let puzzle = generate_puzzle(n, expected_complexity.0).unwrap();
                   println!("Puzzle: {puzzle:?}");
                   let computed_complexity =
                       classify_puzzle(&puzzle).expect("Failed to classify puzzle: {puzzle:?}");
@@ -69,6 +193,31 @@ mod tests {
       });
   }
 
+  #[test]
+  fn test_classify_with_scheme() {
+      let scheme = ClassificationScheme::new(5).unwrap();
+      (1..=5).for_each(|c| {
+          let puzzle = generate_puzzle_with(3, c, &scheme).unwrap();
+          let computed_complexity = classify_puzzle_with(&puzzle, &scheme).unwrap();
+          assert_eq!(computed_complexity, ComplexityClass::with_scheme(c, &scheme).unwrap());
+      });
+  }
+
+  #[test]
+  fn test_classify_by_deduction() {
+      test_suite().into_iter().for_each(|n| {
+        (1..=3).for_each(|c| {
+          let puzzle = generate_puzzle(n, c).unwrap();
+          let rating = classify_puzzle_by_deduction(&puzzle).unwrap();
+          let missing = puzzle.0.iter().filter(|tile| tile.is_none()).count();
+          assert_eq!(
+              rating.only_tile_for_position + rating.only_position_for_tile + rating.guesses,
+              missing
+          );
+      });
+    });
+  }
+
   #[test]
   fn test_all() {
       test_suite().into_iter().for_each(|n| {
@@ -76,7 +225,7 @@ mod tests {
               .into_iter()
               .map(|c| ComplexityClass::new(c).unwrap())
               .for_each(|expected_complexity| {
-                  let puzzle = generate_puzzle(n, expected_complexity.0);
+                  let puzzle = generate_puzzle(n, expected_complexity.0).unwrap();
                   let solution = solve_puzzle(&puzzle).unwrap();
                   let _ = validate_puzzle(&puzzle, &solution)
                       .map(|_solution| {